@@ -0,0 +1,35 @@
+use std::any::Any;
+use std::rc::Rc;
+use crate::view::stylesheet::StyleSheet;
+use crate::view::event::{Event, EventKind, Subscription};
+use crate::view::view::Error;
+
+// Element is a handle to a node owned by a backend
+pub type Element = Rc<dyn Node>;
+
+// Backend creates the elements a widget tree is rendered into.
+// web::WebBackend renders into the browser DOM, memory::MemoryBackend into an in-memory tree.
+pub trait Backend {
+    fn create_element(&self, tag: &str) -> Result<Element, Error>;
+    fn body(&self) -> Result<Element, Error>;
+    fn head(&self) -> Result<Element, Error>;
+    fn set_title(&self, title: &str) -> Result<(), Error>;
+
+    // Sheet the generated CSS rules of widgets go into
+    fn style_sheet(&self) -> Rc<StyleSheet>;
+}
+
+pub trait Node {
+    fn as_any(&self) -> &dyn Any;
+    fn tag(&self) -> String;
+    fn set_text(&self, text: &str);
+    fn attribute(&self, name: &str) -> Option<String>;
+    fn set_attribute(&self, name: &str, value: &str) -> Result<(), Error>;
+    fn remove_attribute(&self, name: &str) -> Result<(), Error>;
+    fn append_child(&self, child: &Element) -> Result<(), Error>;
+    fn remove_child(&self, child: &Element) -> Result<(), Error>;
+    fn replace_child(&self, new: &Element, old: &Element) -> Result<(), Error>;
+    fn set_style(&self, name: &str, value: &str) -> Result<(), Error>;
+    fn remove_style(&self, name: &str) -> Result<(), Error>;
+    fn listen(&self, kind: EventKind, handler: Rc<dyn Fn(&Event)>) -> Subscription;
+}
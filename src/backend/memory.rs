@@ -0,0 +1,458 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::fmt::Formatter;
+use std::rc::{Rc, Weak};
+use crate::backend::backend::{Backend, Element, Node};
+use crate::view::stylesheet::StyleSheet;
+use crate::view::event::{Event, EventKind, Subscription};
+use crate::view::view::Error;
+
+type Handler = Rc<dyn Fn(&Event)>;
+type Handlers = RefCell<Vec<(usize, EventKind, Handler)>>;
+
+// MemoryBackend renders into a tree of MemoryNode values instead of a browser DOM.
+// It lets a widget tree be built and inspected anywhere, including plain `cargo test`.
+pub struct MemoryBackend {
+    head: Element,
+    body: Element,
+    title: RefCell<Option<Element>>,
+    // Elements still waiting to be claimed while hydrating, in document order
+    pending: RefCell<Option<VecDeque<Element>>>,
+    style_sheet: Rc<StyleSheet>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> MemoryBackend {
+        MemoryBackend {
+            head: Rc::new(MemoryNode::new("head")),
+            body: Rc::new(MemoryNode::new("body")),
+            title: RefCell::new(None),
+            pending: RefCell::new(None),
+            style_sheet: Rc::default(),
+        }
+    }
+
+    // Writes generated rules into sheet, which may be compiled or shared with another backend
+    pub fn with_style_sheet(mut self, sheet: Rc<StyleSheet>) -> MemoryBackend {
+        self.style_sheet = sheet;
+        self
+    }
+
+    // A memory backend that styles elements the same way as backend
+    pub fn mirror(backend: &dyn Backend) -> MemoryBackend {
+        MemoryBackend { style_sheet: backend.style_sheet(), ..MemoryBackend::new() }
+    }
+
+    pub fn root(&self) -> &MemoryNode {
+        MemoryNode::of(&self.body).unwrap()
+    }
+
+    pub fn head_root(&self) -> &MemoryNode {
+        MemoryNode::of(&self.head).unwrap()
+    }
+
+    // Makes create_element claim the elements already under body, in document order,
+    // like WebBackend::start_hydration does with server-rendered markup
+    pub fn start_hydration(&self) -> Result<(), Error> {
+        fn collect(element: &Element, pending: &mut VecDeque<Element>) {
+            for child in MemoryNode::of(element).map(MemoryNode::children).unwrap_or_default() {
+                if !RAW_TEXT_TAGS.contains(&child.tag().as_str()) {
+                    pending.push_back(Rc::clone(&child));
+                    collect(&child, pending);
+                }
+            }
+        }
+
+        let mut pending = VecDeque::new();
+        collect(&self.body, &mut pending);
+        *self.pending.borrow_mut() = Some(pending);
+        Ok(())
+    }
+
+    // Ends hydration. Fails if body had elements the widget tree did not claim.
+    pub fn finish_hydration(&self) -> Result<(), Error> {
+        match self.pending.borrow_mut().take() {
+            Some(pending) if !pending.is_empty() => Err(Error::HydrationMismatch(pending[0].tag())),
+            _ => Ok(())
+        }
+    }
+
+    // Ends hydration and empties body so the tree can be built from scratch
+    pub fn abort_hydration(&self) -> Result<(), Error> {
+        self.pending.borrow_mut().take();
+        self.root().children.borrow_mut().retain(|child| RAW_TEXT_TAGS.contains(&child.tag().as_str()));
+        Ok(())
+    }
+}
+
+impl Default for MemoryBackend {
+    fn default() -> Self {
+        MemoryBackend::new()
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn create_element(&self, tag: &str) -> Result<Element, Error> {
+        if let Some(pending) = self.pending.borrow_mut().as_mut() {
+            return match pending.pop_front() {
+                Some(e) if e.tag().eq_ignore_ascii_case(tag) => Ok(e),
+                _ => Err(Error::HydrationMismatch(tag.to_string())),
+            };
+        }
+
+        Ok(Rc::new(MemoryNode::new(tag)))
+    }
+
+    fn body(&self) -> Result<Element, Error> {
+        Ok(Rc::clone(&self.body))
+    }
+
+    fn head(&self) -> Result<Element, Error> {
+        Ok(Rc::clone(&self.head))
+    }
+
+    fn set_title(&self, title: &str) -> Result<(), Error> {
+        let mut element = self.title.borrow_mut();
+        if element.is_none() {
+            let title = self.create_element("title")?;
+            self.head.append_child(&title)?;
+            *element = Some(title);
+        }
+        if let Some(e) = element.as_ref() {
+            e.set_text(title);
+        }
+        Ok(())
+    }
+
+    fn style_sheet(&self) -> Rc<StyleSheet> {
+        Rc::clone(&self.style_sheet)
+    }
+}
+
+pub struct MemoryNode {
+    tag: String,
+    text: RefCell<Option<String>>,
+    attributes: RefCell<BTreeMap<String, String>>,
+    styles: RefCell<BTreeMap<String, String>>,
+    children: RefCell<Vec<Element>>,
+    handlers: Rc<Handlers>,
+    next_handler: Cell<usize>,
+}
+
+impl MemoryNode {
+    pub fn new(tag: &str) -> MemoryNode {
+        MemoryNode {
+            tag: tag.to_string(),
+            text: RefCell::default(),
+            attributes: RefCell::default(),
+            styles: RefCell::default(),
+            children: RefCell::default(),
+            handlers: Rc::default(),
+            next_handler: Cell::new(0),
+        }
+    }
+
+    pub fn of(element: &Element) -> Option<&MemoryNode> {
+        element.as_any().downcast_ref::<MemoryNode>()
+    }
+
+    pub fn text(&self) -> Option<String> {
+        self.text.borrow().clone()
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        self.attributes.borrow().get(name).cloned()
+    }
+
+    pub fn attributes(&self) -> BTreeMap<String, String> {
+        self.attributes.borrow().clone()
+    }
+
+    pub fn style(&self, name: &str) -> Option<String> {
+        self.styles.borrow().get(name).cloned()
+    }
+
+    pub fn styles(&self) -> BTreeMap<String, String> {
+        self.styles.borrow().clone()
+    }
+
+    pub fn children(&self) -> Vec<Element> {
+        self.children.borrow().clone()
+    }
+
+    // Calls every handler listening for the event's kind, like a browser would
+    pub fn dispatch(&self, event: Event) {
+        let handlers: Vec<Handler> = self.handlers.borrow().iter()
+            .filter(|(_, kind, _)| *kind == event.kind)
+            .map(|(_, _, handler)| Rc::clone(handler))
+            .collect();
+        for handler in handlers {
+            handler(&event);
+        }
+    }
+}
+
+impl Node for MemoryNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn tag(&self) -> String {
+        self.tag.clone()
+    }
+
+    fn set_text(&self, text: &str) {
+        self.children.borrow_mut().clear();
+        *self.text.borrow_mut() = Some(text.to_string());
+    }
+
+    fn attribute(&self, name: &str) -> Option<String> {
+        MemoryNode::attribute(self, name)
+    }
+
+    fn set_attribute(&self, name: &str, value: &str) -> Result<(), Error> {
+        self.attributes.borrow_mut().insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove_attribute(&self, name: &str) -> Result<(), Error> {
+        self.attributes.borrow_mut().remove(name);
+        Ok(())
+    }
+
+    fn append_child(&self, child: &Element) -> Result<(), Error> {
+        MemoryNode::of(child).ok_or(Error::ForeignElement)?;
+        // Like in the DOM, a child that is already there moves to the end
+        let mut children = self.children.borrow_mut();
+        children.retain(|c| !Rc::ptr_eq(c, child));
+        children.push(Rc::clone(child));
+        Ok(())
+    }
+
+    fn remove_child(&self, child: &Element) -> Result<(), Error> {
+        let mut children = self.children.borrow_mut();
+        let i = children.iter().position(|c| Rc::ptr_eq(c, child)).ok_or(Error::RemoveChild)?;
+        children.remove(i);
+        Ok(())
+    }
+
+    fn replace_child(&self, new: &Element, old: &Element) -> Result<(), Error> {
+        MemoryNode::of(new).ok_or(Error::ForeignElement)?;
+        let mut children = self.children.borrow_mut();
+        let i = children.iter().position(|c| Rc::ptr_eq(c, old)).ok_or(Error::RemoveChild)?;
+        children[i] = Rc::clone(new);
+        Ok(())
+    }
+
+    fn set_style(&self, name: &str, value: &str) -> Result<(), Error> {
+        self.styles.borrow_mut().insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove_style(&self, name: &str) -> Result<(), Error> {
+        self.styles.borrow_mut().remove(name);
+        Ok(())
+    }
+
+    fn listen(&self, kind: EventKind, handler: Handler) -> Subscription {
+        let id = self.next_handler.get();
+        self.next_handler.set(id + 1);
+        self.handlers.borrow_mut().push((id, kind, handler));
+        Subscription::new(HandlerGuard { id, handlers: Rc::downgrade(&self.handlers) })
+    }
+}
+
+// Elements that never have children or a closing tag
+const VOID_TAGS: [&str; 6] = ["br", "hr", "img", "input", "link", "meta"];
+
+// Elements whose text is not markup and must not be escaped
+const RAW_TEXT_TAGS: [&str; 2] = ["script", "style"];
+
+// Display serializes the node and its subtree as HTML, with styles as an inline style attribute
+impl fmt::Display for MemoryNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}", self.tag)?;
+        for (name, value) in self.attributes.borrow().iter() {
+            write!(f, " {}=\"{}\"", name, escape(value, true))?;
+        }
+
+        let styles = self.styles.borrow();
+        if !styles.is_empty() {
+            let declarations: Vec<String> = styles.iter()
+                .map(|(name, value)| format!("{}: {}", name, value))
+                .collect();
+            write!(f, " style=\"{}\"", escape(&declarations.join("; "), true))?;
+        }
+        write!(f, ">")?;
+
+        if VOID_TAGS.contains(&self.tag.as_str()) {
+            return Ok(());
+        }
+
+        if let Some(text) = self.text.borrow().as_ref() {
+            if RAW_TEXT_TAGS.contains(&self.tag.as_str()) {
+                write!(f, "{}", text)?;
+            } else {
+                write!(f, "{}", escape(text, false))?;
+            }
+        }
+        for child in self.children.borrow().iter() {
+            if let Some(child) = MemoryNode::of(child) {
+                write!(f, "{}", child)?;
+            }
+        }
+        write!(f, "</{}>", self.tag)
+    }
+}
+
+fn escape(s: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// HandlerGuard removes its handler from the node when the subscription is dropped
+struct HandlerGuard {
+    id: usize,
+    handlers: Weak<Handlers>,
+}
+
+impl Drop for HandlerGuard {
+    fn drop(&mut self) {
+        if let Some(handlers) = self.handlers.upgrade() {
+            handlers.borrow_mut().retain(|(id, _, _)| *id != self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use crate::backend::backend::{Backend, Node};
+    use crate::backend::memory::{MemoryBackend, MemoryNode};
+    use crate::view::event::{Event, EventKind};
+    use crate::view::view::{Error, Viewable};
+    use crate::view::widget::{Body, Button, Column, Link, Listenable, Text};
+
+    fn page(clicks: &Rc<Cell<u32>>) -> Body {
+        let mut button = Button::new("Save");
+        button.on_click({
+            let clicks = Rc::clone(clicks);
+            move |_| clicks.set(clicks.get() + 1)
+        });
+        Body::new(Column::new().child(Text::new("Hello")).child(button))
+    }
+
+    #[test]
+    fn builds_a_button() {
+        let backend = MemoryBackend::new();
+        let clicks = Rc::new(Cell::new(0));
+        let mut button = Button::new("Save");
+        button.on_click({
+            let clicks = Rc::clone(&clicks);
+            move |_| clicks.set(clicks.get() + 1)
+        });
+
+        let element = button.build(&backend).unwrap();
+        let node = MemoryNode::of(&element).unwrap();
+        assert_eq!(node.tag(), "button");
+        assert_eq!(node.text().as_deref(), Some("Save"));
+        assert_eq!(node.style("cursor").as_deref(), Some("pointer"));
+        assert_eq!(node.style("text-transform").as_deref(), Some("uppercase"));
+
+        node.dispatch(Event::new(EventKind::Click, None, None));
+        node.dispatch(Event::new(EventKind::Input, None, None));
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn dropping_the_widget_detaches_its_listeners() {
+        let backend = MemoryBackend::new();
+        let clicks = Rc::new(Cell::new(0));
+        let mut button = Button::new("Save");
+        button.on_click({
+            let clicks = Rc::clone(&clicks);
+            move |_| clicks.set(clicks.get() + 1)
+        });
+        let element = button.build(&backend).unwrap();
+
+        drop(button);
+        MemoryNode::of(&element).unwrap().dispatch(Event::new(EventKind::Click, None, None));
+        assert_eq!(clicks.get(), 0);
+    }
+
+    #[test]
+    fn display_escapes_text_and_attributes() {
+        let backend = MemoryBackend::new();
+        let link = backend.create_element("a").unwrap();
+        link.set_attribute("title", "say \"hi\" & <wave>").unwrap();
+        link.set_text("1 < 2 & \"quotes\"");
+        let script = backend.create_element("script").unwrap();
+        script.set_text("if (a < b) {}");
+
+        assert_eq!(MemoryNode::of(&link).unwrap().to_string(),
+                   "<a title=\"say &quot;hi&quot; &amp; &lt;wave&gt;\">1 &lt; 2 &amp; \"quotes\"</a>");
+        assert_eq!(MemoryNode::of(&script).unwrap().to_string(), "<script>if (a < b) {}</script>");
+    }
+
+    #[test]
+    fn hydration_adopts_the_existing_elements() {
+        let backend = MemoryBackend::new();
+        // Stands in for markup rendered on the server, without any listeners
+        page(&Rc::new(Cell::new(0))).build(&backend).unwrap();
+        let column = backend.root().children()[0].clone();
+        let rendered = backend.root().to_string();
+
+        let clicks = Rc::new(Cell::new(0));
+        let mut body = page(&clicks);
+        backend.start_hydration().unwrap();
+        body.build(&backend).unwrap();
+        backend.finish_hydration().unwrap();
+
+        let children = backend.root().children();
+        assert_eq!(children.len(), 1);
+        assert!(Rc::ptr_eq(&children[0], &column));
+        assert_eq!(backend.root().to_string(), rendered);
+
+        let button = MemoryNode::of(&column).unwrap().children()[1].clone();
+        MemoryNode::of(&button).unwrap().dispatch(Event::new(EventKind::Click, None, None));
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn a_hydration_mismatch_falls_back_to_a_fresh_build() {
+        let backend = MemoryBackend::new();
+        page(&Rc::new(Cell::new(0))).build(&backend).unwrap();
+        let column = backend.root().children()[0].clone();
+
+        // A link where the markup has a button
+        let mut body = Body::new(Column::new().child(Text::new("Hello")).child(Link::new("Save", "/save")));
+        backend.start_hydration().unwrap();
+        assert!(matches!(body.build(&backend), Err(Error::HydrationMismatch(tag)) if tag == "a"));
+        drop(body);
+        backend.abort_hydration().unwrap();
+
+        let mut body = Body::new(Column::new().child(Text::new("Hello")).child(Link::new("Save", "/save")));
+        body.build(&backend).unwrap();
+        let children = backend.root().children();
+        assert_eq!(children.len(), 1);
+        assert!(!Rc::ptr_eq(&children[0], &column));
+        assert_eq!(MemoryNode::of(&children[0]).unwrap().children()[1].tag(), "a");
+
+        // Markup the tree leaves unclaimed is a mismatch as well
+        backend.start_hydration().unwrap();
+        Body::new(Column::new()).build(&backend).unwrap();
+        assert!(matches!(backend.finish_hydration(), Err(Error::HydrationMismatch(_))));
+    }
+}
@@ -0,0 +1,239 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use gloo::events::EventListener;
+use wasm_bindgen::JsCast;
+use crate::backend::backend::{Backend, Element, Node};
+use crate::view::event::{Event, EventKind, Modifiers, Subscription};
+use crate::view::stylesheet::{self, StyleSheet};
+use crate::view::view::Error;
+use crate::window::head;
+
+// Tags inside body that never belong to a widget tree
+const FOREIGN_TAGS: [&str; 4] = ["script", "noscript", "style", "template"];
+
+pub struct WebBackend {
+    document: web_sys::Document,
+    // Server-rendered elements still waiting to be claimed while hydrating, in document order
+    pending: RefCell<Option<VecDeque<web_sys::HtmlElement>>>,
+    style_sheet: RefCell<Rc<StyleSheet>>,
+}
+
+impl WebBackend {
+    pub fn new() -> Option<WebBackend> {
+        let document = web_sys::window()?.document()?;
+        let style_sheet = RefCell::new(Rc::new(StyleSheet::new()));
+        Some(WebBackend { document, pending: RefCell::new(None), style_sheet })
+    }
+
+    pub fn document(&self) -> &web_sys::Document {
+        &self.document
+    }
+
+    // Compiles all widget styles into the style sheet instead of inline properties.
+    // A sheet rendered on the server is taken over.
+    pub fn enable_style_sheet(&self) {
+        let selector = format!("head style[{}]", stylesheet::MARKER);
+        let existing = self.document.query_selector(&selector).ok().flatten()
+            .and_then(|e| e.dyn_into::<web_sys::HtmlElement>().ok());
+
+        let sheet = match existing {
+            Some(e) => StyleSheet::compiled().adopt(Rc::new(WebElement(e))),
+            None => StyleSheet::compiled(),
+        };
+        *self.style_sheet.borrow_mut() = Rc::new(sheet);
+    }
+
+    // Makes create_element claim the elements already under body, in document order,
+    // instead of creating new ones. Each claimed element must have the requested tag.
+    pub fn start_hydration(&self) -> Result<(), Error> {
+        let body = self.document.body().ok_or(Error::NoBodyFound)?;
+        let nodes = body.query_selector_all("*").map_err(|_| Error::NoBodyFound)?;
+
+        let mut pending = VecDeque::new();
+        for i in 0..nodes.length() {
+            let element = match nodes.item(i).and_then(|n| n.dyn_into::<web_sys::HtmlElement>().ok()) {
+                Some(e) => e,
+                None => continue,
+            };
+            if !FOREIGN_TAGS.contains(&element.tag_name().to_lowercase().as_str()) {
+                pending.push_back(element);
+            }
+        }
+
+        *self.pending.borrow_mut() = Some(pending);
+        Ok(())
+    }
+
+    // Ends hydration. Fails if the markup had elements the widget tree did not claim.
+    pub fn finish_hydration(&self) -> Result<(), Error> {
+        match self.pending.borrow_mut().take() {
+            Some(pending) if !pending.is_empty() => {
+                Err(Error::HydrationMismatch(pending[0].tag_name().to_lowercase()))
+            }
+            _ => Ok(())
+        }
+    }
+
+    // Removes head tags a server-rendered page left behind, before the page mounts its own
+    pub fn remove_head_tags(&self) -> Result<(), Error> {
+        let selector = format!("head [{}]", head::MARKER);
+        let nodes = self.document.query_selector_all(&selector).map_err(|_| Error::NoHeadFound)?;
+        for i in 0..nodes.length() {
+            if let Some(element) = nodes.item(i).and_then(|n| n.dyn_into::<web_sys::Element>().ok()) {
+                element.remove();
+            }
+        }
+        Ok(())
+    }
+
+    // Ends hydration and removes the server-rendered markup so the tree can be built from scratch
+    pub fn abort_hydration(&self) -> Result<(), Error> {
+        self.pending.borrow_mut().take();
+
+        let body = self.document.body().ok_or(Error::NoBodyFound)?;
+        let children = body.children();
+        for i in (0..children.length()).rev() {
+            if let Some(child) = children.item(i) {
+                if !FOREIGN_TAGS.contains(&child.tag_name().to_lowercase().as_str()) {
+                    child.remove();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Backend for WebBackend {
+    fn create_element(&self, tag: &str) -> Result<Element, Error> {
+        if let Some(pending) = self.pending.borrow_mut().as_mut() {
+            return match pending.pop_front() {
+                Some(e) if e.tag_name().eq_ignore_ascii_case(tag) => Ok(Rc::new(WebElement(e))),
+                _ => Err(Error::HydrationMismatch(tag.to_string())),
+            };
+        }
+
+        let element = match self.document.create_element(tag) {
+            Ok(e) => e.dyn_into::<web_sys::HtmlElement>().map_err(|_| Error::ElementCreation)?,
+            Err(_) => return Err(Error::ElementCreation),
+        };
+        Ok(Rc::new(WebElement(element)))
+    }
+
+    fn body(&self) -> Result<Element, Error> {
+        let body = self.document.body().ok_or(Error::NoBodyFound)?;
+        Ok(Rc::new(WebElement(body)))
+    }
+
+    fn head(&self) -> Result<Element, Error> {
+        let head = self.document.head().ok_or(Error::NoHeadFound)?;
+        Ok(Rc::new(WebElement(head.into())))
+    }
+
+    fn set_title(&self, title: &str) -> Result<(), Error> {
+        self.document.set_title(title);
+        Ok(())
+    }
+
+    fn style_sheet(&self) -> Rc<StyleSheet> {
+        Rc::clone(&self.style_sheet.borrow())
+    }
+}
+
+pub struct WebElement(web_sys::HtmlElement);
+
+impl WebElement {
+    pub fn of(element: &Element) -> Option<&WebElement> {
+        element.as_any().downcast_ref::<WebElement>()
+    }
+
+    pub fn html_element(&self) -> &web_sys::HtmlElement {
+        &self.0
+    }
+}
+
+impl Node for WebElement {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn tag(&self) -> String {
+        self.0.tag_name().to_lowercase()
+    }
+
+    fn set_text(&self, text: &str) {
+        self.0.set_text_content(Some(text));
+    }
+
+    fn attribute(&self, name: &str) -> Option<String> {
+        self.0.get_attribute(name)
+    }
+
+    fn set_attribute(&self, name: &str, value: &str) -> Result<(), Error> {
+        self.0.set_attribute(name, value).map_err(|_| Error::Attribute(name.to_string()))
+    }
+
+    fn remove_attribute(&self, name: &str) -> Result<(), Error> {
+        self.0.remove_attribute(name).map_err(|_| Error::Attribute(name.to_string()))
+    }
+
+    fn append_child(&self, child: &Element) -> Result<(), Error> {
+        let child = WebElement::of(child).ok_or(Error::ForeignElement)?;
+        self.0.append_child(&child.0).map_err(|_| Error::AppendChild)?;
+        Ok(())
+    }
+
+    fn remove_child(&self, child: &Element) -> Result<(), Error> {
+        let child = WebElement::of(child).ok_or(Error::ForeignElement)?;
+        self.0.remove_child(&child.0).map_err(|_| Error::RemoveChild)?;
+        Ok(())
+    }
+
+    fn replace_child(&self, new: &Element, old: &Element) -> Result<(), Error> {
+        let new = WebElement::of(new).ok_or(Error::ForeignElement)?;
+        let old = WebElement::of(old).ok_or(Error::ForeignElement)?;
+        self.0.replace_child(&new.0, &old.0).map_err(|_| Error::RemoveChild)?;
+        Ok(())
+    }
+
+    fn set_style(&self, name: &str, value: &str) -> Result<(), Error> {
+        self.0.style().set_property(name, value).map_err(|_| Error::Style(name.to_string()))
+    }
+
+    fn remove_style(&self, name: &str) -> Result<(), Error> {
+        self.0.style().remove_property(name).map_err(|_| Error::Style(name.to_string()))?;
+        Ok(())
+    }
+
+    fn listen(&self, kind: EventKind, handler: Rc<dyn Fn(&Event)>) -> Subscription {
+        Subscription::new(EventListener::new(&self.0, kind.name(), move |e| {
+            let event = web_event(kind, e);
+            handler(&event);
+            if event.is_default_prevented() {
+                e.prevent_default();
+            }
+        }))
+    }
+}
+
+fn web_event(kind: EventKind, event: &web_sys::Event) -> Event {
+    let value = event.target().and_then(|target| {
+        if let Some(input) = target.dyn_ref::<web_sys::HtmlInputElement>() {
+            Some(input.value())
+        } else {
+            target.dyn_ref::<web_sys::HtmlTextAreaElement>().map(|area| area.value())
+        }
+    });
+    let key = event.dyn_ref::<web_sys::KeyboardEvent>().map(|e| e.key());
+    let mut result = Event::new(kind, value, key);
+
+    if let Some(e) = event.dyn_ref::<web_sys::MouseEvent>() {
+        let modifiers = Modifiers { ctrl: e.ctrl_key(), shift: e.shift_key(), alt: e.alt_key(), meta: e.meta_key() };
+        result = result.with_button(e.button()).with_modifiers(modifiers);
+    } else if let Some(e) = event.dyn_ref::<web_sys::KeyboardEvent>() {
+        let modifiers = Modifiers { ctrl: e.ctrl_key(), shift: e.shift_key(), alt: e.alt_key(), meta: e.meta_key() };
+        result = result.with_modifiers(modifiers);
+    }
+    result
+}
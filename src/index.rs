@@ -1,7 +1,10 @@
-use crate::view::size::Size;
-use crate::view::style::{Align, Justify, Margin};
+use crate::view::breakpoint::Breakpoint;
+use crate::view::size::{Edge, Size};
+use crate::view::style::{Align, Border, BorderRadius, DisplayMode, Justify, Padding};
+use crate::view::theme::Theme;
 use crate::view::view::Viewable;
-use crate::view::widget::{Body, Button, Column, Input, Styleable, Title};
+use crate::view::widget::{Body, Button, Column, Container, Input, Styleable, Title};
+use crate::window::head::Head;
 use crate::window::page::Page;
 
 pub(crate) struct Login {}
@@ -13,15 +16,34 @@ impl Login {
 }
 
 impl Page for Login {
+    fn head(&self) -> Head {
+        Head::new()
+            .title("Login")
+            .viewport("width=device-width, initial-scale=1")
+    }
+
     fn body(&self) -> Box<dyn Viewable> {
         Body::new(
-            Column::new()
-                .child(Title::h2("Login"))
-                .child(Input::new("Username"))
-                .child(Input::new("Password"))
-                .child(Button::new("Submit"))
+            Container::new(
+                Column::new()
+                    .child(Title::h2("Login"))
+                    .child(Input::new("Username"))
+                    .child(Input::new("Password"))
+                    .child(Button::new("Submit"))
+            )
+                .padding(Edge::all(Size::Pixel(16.0)))
+                .apply(&|mut card| {
+                    let theme = Theme::current();
+                    // A bordered card from tablets on, the bare form on phones
+                    card
+                        .style_at(Breakpoint::Md, Padding::new(theme.spacing.lg.clone()))
+                        .style_at(Breakpoint::Md, Border::solid(Size::Pixel(1.0), theme.palette().border))
+                        .style_at(Breakpoint::Md, BorderRadius::new(theme.radius.md.clone()));
+                    return card;
+                })
         ).apply(&|mut body| {
-            body.style(Justify::center())
+            body.style(DisplayMode::flex())
+                .style(Justify::center())
                 .style(Align::center());
             return body;
         })
@@ -1,5 +1,6 @@
 pub mod window;
 pub mod view;
+pub mod backend;
 mod index;
 
 use window::window::*;
@@ -9,5 +10,7 @@ use wasm_bindgen::prelude::*;
 #[wasm_bindgen]
 pub fn main() {
     let w = Window::new(index::Login::new()).expect("Failed to init window");
-    w.build().expect("Failed to build window");
+    let w = w.build().expect("Failed to build window");
+    // Keep the window, and with it every event listener, alive for the lifetime of the page
+    std::mem::forget(w);
 }
@@ -0,0 +1,60 @@
+use std::cell::Cell;
+
+thread_local! {
+    // The table every breakpoint style is compiled with
+    static BREAKPOINTS: Cell<Breakpoints> = const { Cell::new(Breakpoints::new()) };
+}
+
+// Breakpoint is a viewport width from which a style applies, see Styleable::style_at.
+// Styles are mobile first: a style at Md applies on medium screens and everything wider.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Breakpoint {
+    Sm,
+    Md,
+    Lg,
+    Xl,
+}
+
+impl Breakpoint {
+    // Minimum viewport width in pixels, taken from the configured table
+    pub fn min_width(&self) -> u32 {
+        let breakpoints = BREAKPOINTS.with(|b| b.get());
+        match self {
+            Breakpoint::Sm => breakpoints.sm,
+            Breakpoint::Md => breakpoints.md,
+            Breakpoint::Lg => breakpoints.lg,
+            Breakpoint::Xl => breakpoints.xl,
+        }
+    }
+
+    pub fn media_query(&self) -> String {
+        format!("@media (min-width: {}px)", self.min_width())
+    }
+}
+
+// Breakpoints is the table of minimum widths in pixels
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Breakpoints {
+    pub sm: u32,
+    pub md: u32,
+    pub lg: u32,
+    pub xl: u32,
+}
+
+impl Breakpoints {
+    pub const fn new() -> Breakpoints {
+        Breakpoints { sm: 640, md: 768, lg: 1024, xl: 1280 }
+    }
+
+    // Makes this the table styles are compiled with. Configure it before building any page,
+    // and the same way on the server, so rendered and hydrated classes agree.
+    pub fn install(self) {
+        BREAKPOINTS.with(|b| b.set(self));
+    }
+}
+
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Breakpoints::new()
+    }
+}
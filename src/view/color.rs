@@ -1,9 +1,10 @@
 use std::fmt;
 use std::fmt::Formatter;
+use std::str::FromStr;
+use crate::view::view::Error;
 
-// REFACTOR
-
-#[derive(Clone)]
+// Colors is a CSS color value: a keyword, an Srgb or any CSS color text
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Colors {
     None,
     White,
@@ -11,9 +12,50 @@ pub enum Colors {
     RoyalBlue,
     Lightgray,
     Transparent,
+    Rgba(Srgb),
     Custom(&'static str),
 }
 
+impl Colors {
+    // The color as RGBA, if it is one. Custom text is resolved when it is a hex color.
+    pub fn to_color(&self) -> Option<Srgb> {
+        match self {
+            Colors::None => None,
+            Colors::White => Some(Srgb::rgb(255, 255, 255)),
+            Colors::Black => Some(Srgb::rgb(0, 0, 0)),
+            Colors::RoyalBlue => Some(Srgb::rgb(65, 105, 225)),
+            Colors::Lightgray => Some(Srgb::rgb(211, 211, 211)),
+            Colors::Transparent => Some(Srgb::rgba(0, 0, 0, 0.0)),
+            Colors::Rgba(color) => Some(*color),
+            Colors::Custom(s) => Srgb::hex(s).ok(),
+        }
+    }
+}
+
+impl From<Srgb> for Colors {
+    fn from(color: Srgb) -> Self {
+        Colors::Rgba(color)
+    }
+}
+
+// Parses the keywords Display writes and any color Srgb parses. Other CSS color text
+// is refused, a Custom color can only be made in code.
+impl FromStr for Colors {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Colors, Error> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(Colors::None),
+            "white" => Ok(Colors::White),
+            "black" => Ok(Colors::Black),
+            "royalblue" => Ok(Colors::RoyalBlue),
+            "lightgray" => Ok(Colors::Lightgray),
+            "transparent" => Ok(Colors::Transparent),
+            _ => s.parse::<Srgb>().map(Colors::Rgba),
+        }
+    }
+}
+
 impl fmt::Display for Colors {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -22,9 +64,264 @@ impl fmt::Display for Colors {
             Colors::Black => write!(f, "black"),
             Colors::RoyalBlue => write!(f, "royalBlue"),
             Colors::Lightgray => write!(f, "lightgray"),
-            Colors::Lightgray => write!(f, "transparent"),
+            Colors::Transparent => write!(f, "transparent"),
+            Colors::Rgba(color) => write!(f, "{}", color),
             Colors::Custom(s) => write!(f, "{}", s),
-            _ => write!(f, "Color not found")
         }
     }
-}
\ No newline at end of file
+}
+
+// Srgb is an sRGB color with alpha. Channels are 0 to 255, alpha 0 to 1.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Srgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: f32,
+}
+
+impl Srgb {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Srgb {
+        Srgb { r, g, b, a: 1.0 }
+    }
+
+    // Alpha is clamped to 0 to 1, NaN counts as transparent
+    pub const fn rgba(r: u8, g: u8, b: u8, a: f32) -> Srgb {
+        let a = if a.is_nan() { 0.0 } else { a.clamp(0.0, 1.0) };
+        Srgb { r, g, b, a }
+    }
+
+    // Hue in degrees, saturation and lightness in percent, like CSS hsl()
+    pub fn hsl(h: f32, s: f32, l: f32) -> Srgb {
+        Srgb::hsla(h, s, l, 1.0)
+    }
+
+    pub fn hsla(h: f32, s: f32, l: f32, a: f32) -> Srgb {
+        let h = h.rem_euclid(360.0) / 360.0;
+        let s = (s / 100.0).clamp(0.0, 1.0);
+        let l = (l / 100.0).clamp(0.0, 1.0);
+
+        let q = if l < 0.5 { l * (1.0 + s) } else { l + s - l * s };
+        let p = 2.0 * l - q;
+        let channel = |t: f32| {
+            let t = t.rem_euclid(1.0);
+            let v = if t < 1.0 / 6.0 {
+                p + (q - p) * 6.0 * t
+            } else if t < 0.5 {
+                q
+            } else if t < 2.0 / 3.0 {
+                p + (q - p) * (2.0 / 3.0 - t) * 6.0
+            } else {
+                p
+            };
+            (v * 255.0).round() as u8
+        };
+
+        Srgb::rgba(channel(h + 1.0 / 3.0), channel(h), channel(h - 1.0 / 3.0), a)
+    }
+
+    // Parses "#rgb", "#rgba", "#rrggbb" or "#rrggbbaa"
+    pub fn hex(hex: &str) -> Result<Srgb, Error> {
+        let invalid = || Error::InvalidColor(hex.to_string());
+        let digits = hex.strip_prefix('#').ok_or_else(invalid)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        let channels: Vec<u8> = match digits.len() {
+            3 | 4 => digits.chars()
+                .map(|c| u8::from_str_radix(&c.to_string().repeat(2), 16).map_err(|_| invalid()))
+                .collect::<Result<_, _>>()?,
+            6 | 8 => (0..digits.len()).step_by(2)
+                .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).map_err(|_| invalid()))
+                .collect::<Result<_, _>>()?,
+            _ => return Err(invalid()),
+        };
+
+        let a = channels.get(3).map_or(1.0, |a| *a as f32 / 255.0);
+        Ok(Srgb::rgba(channels[0], channels[1], channels[2], a))
+    }
+
+    // Hue in degrees, saturation and lightness in percent
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let r = self.r as f32 / 255.0;
+        let g = self.g as f32 / 255.0;
+        let b = self.b as f32 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+
+        if max == min {
+            return (0.0, 0.0, l * 100.0);
+        }
+
+        let d = max - min;
+        let s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max + min) };
+        let h = if max == r {
+            (g - b) / d + if g < b { 6.0 } else { 0.0 }
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        (h * 60.0, s * 100.0, l * 100.0)
+    }
+
+    pub fn with_alpha(&self, a: f32) -> Srgb {
+        Srgb::rgba(self.r, self.g, self.b, a)
+    }
+
+    // Raises the lightness by amount, 0 to 1 of the full range
+    pub fn lighten(&self, amount: f32) -> Srgb {
+        let (h, s, l) = self.to_hsl();
+        Srgb::hsla(h, s, l + amount * 100.0, self.a)
+    }
+
+    pub fn darken(&self, amount: f32) -> Srgb {
+        self.lighten(-amount)
+    }
+
+    // Lays this color over background, giving the color that is seen
+    pub fn over(&self, background: &Srgb) -> Srgb {
+        background.mix(&self.with_alpha(1.0), self.a).with_alpha(1.0)
+    }
+
+    // Relative luminance as defined by WCAG 2, from 0 for black to 1 for white
+    pub fn luminance(&self) -> f32 {
+        let linear = |c: u8| {
+            let c = c as f32 / 255.0;
+            if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+        };
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    // WCAG contrast ratio of this color shown on background, from 1 for no contrast
+    // to 21 for black on white
+    pub fn contrast(&self, background: &Srgb) -> f32 {
+        let foreground = self.over(background).luminance();
+        let background = background.luminance();
+        (foreground.max(background) + 0.05) / (foreground.min(background) + 0.05)
+    }
+
+    // Blends towards other, weight 0 keeps this color and 1 gives other
+    pub fn mix(&self, other: &Srgb, weight: f32) -> Srgb {
+        let weight = weight.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * weight).round() as u8;
+        Srgb::rgba(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+            self.a + (other.a - self.a) * weight,
+        )
+    }
+}
+
+impl fmt::Display for Srgb {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.a >= 1.0 {
+            write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+        } else {
+            write!(f, "rgba({}, {}, {}, {})", self.r, self.g, self.b, (self.a * 1000.0).round() / 1000.0)
+        }
+    }
+}
+
+// Parses hex colors and the rgb(), rgba(), hsl() and hsla() functions with comma
+// separated arguments, e.g. "rgba(0, 0, 0, 0.5)" or "hsl(210, 50%, 40%)"
+impl FromStr for Srgb {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Srgb, Error> {
+        let invalid = || Error::InvalidColor(s.to_string());
+        let value = s.trim().to_ascii_lowercase();
+        if value.starts_with('#') {
+            return Srgb::hex(&value).map_err(|_| invalid());
+        }
+
+        let (function, args) = value.strip_suffix(')')
+            .and_then(|v| v.split_once('('))
+            .ok_or_else(invalid)?;
+        let args: Vec<&str> = args.split(',').map(str::trim).collect();
+        let alpha = match (function, args.len()) {
+            ("rgb", 3) | ("hsl", 3) => None,
+            ("rgba", 4) | ("hsla", 4) => Some(args[3]),
+            _ => return Err(invalid()),
+        };
+        let a = match alpha {
+            Some(a) => a.parse::<f32>().ok().filter(|a| (0.0..=1.0).contains(a)).ok_or_else(invalid)?,
+            None => 1.0,
+        };
+
+        if function.starts_with("rgb") {
+            let channel = |arg: &str| arg.parse::<u8>().map_err(|_| invalid());
+            Ok(Srgb::rgba(channel(args[0])?, channel(args[1])?, channel(args[2])?, a))
+        } else {
+            let h = args[0].trim_end_matches("deg").parse::<f32>().map_err(|_| invalid())?;
+            let percent = |arg: &str| arg.strip_suffix('%')
+                .and_then(|v| v.parse::<f32>().ok())
+                .filter(|v| (0.0..=100.0).contains(v))
+                .ok_or_else(invalid);
+            Ok(Srgb::hsla(h, percent(args[1])?, percent(args[2])?, a))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::view::color::{Colors, Srgb};
+
+    #[test]
+    fn parses_hex() {
+        assert_eq!(Srgb::hex("#abc").unwrap(), Srgb::rgb(0xAA, 0xBB, 0xCC));
+        assert_eq!(Srgb::hex("#aabbcc").unwrap(), Srgb::rgb(0xAA, 0xBB, 0xCC));
+        assert_eq!(Srgb::hex("#aabbcc80").unwrap(), Srgb::rgba(0xAA, 0xBB, 0xCC, 128.0 / 255.0));
+        assert_eq!(Srgb::hex("#abcd").unwrap(), Srgb::hex("#aabbccdd").unwrap());
+        assert_eq!(Srgb::hex("#AABBCC").unwrap().to_string(), "#AABBCC");
+    }
+
+    #[test]
+    fn rejects_bad_hex() {
+        for hex in ["abc", "#", "#ab", "#abcde", "#aabbccd", "#aabbccddee", "#ggg", "#+1f", "#ää"] {
+            assert!(Srgb::hex(hex).is_err(), "{}", hex);
+        }
+        assert!(Colors::Custom("var(--accent)").to_color().is_none());
+    }
+
+    #[test]
+    fn clamps_alpha() {
+        assert_eq!(Srgb::rgba(0, 0, 0, 1.5).a, 1.0);
+        assert_eq!(Srgb::rgba(0, 0, 0, -0.5).a, 0.0);
+        assert_eq!(Srgb::rgba(0, 0, 0, f32::NAN).a, 0.0);
+        assert_eq!(Srgb::rgb(0, 0, 0).with_alpha(2.0).a, 1.0);
+        assert_eq!(Srgb::rgba(0, 0, 0, 1.5).to_string(), "#000000");
+    }
+
+    #[test]
+    fn mixes() {
+        let black = Srgb::rgb(0, 0, 0);
+        let white = Srgb::rgb(255, 255, 255);
+        assert_eq!(black.mix(&white, 0.0), black);
+        assert_eq!(black.mix(&white, 1.0), white);
+        assert_eq!(black.mix(&white, 0.5), Srgb::rgb(128, 128, 128));
+        assert_eq!(black.mix(&white, 2.0), white);
+        assert_eq!(black.mix(&white.with_alpha(0.0), 0.5).a, 0.5);
+        assert_eq!(white.with_alpha(0.5).over(&black), Srgb::rgb(128, 128, 128));
+    }
+
+    #[test]
+    fn lightens_and_darkens() {
+        let black = Srgb::rgb(0, 0, 0);
+        let white = Srgb::rgb(255, 255, 255);
+        assert_eq!(black.lighten(0.2), Srgb::rgb(51, 51, 51));
+        assert_eq!(white.darken(0.2), Srgb::rgb(204, 204, 204));
+        assert_eq!(black.lighten(2.0), white);
+        assert_eq!(black.darken(0.2), black);
+
+        let blue = Srgb::rgb(0x69, 0x79, 0xF8);
+        let (h, s, l) = blue.darken(0.2).to_hsl();
+        let (bh, bs, bl) = blue.to_hsl();
+        assert!((h - bh).abs() < 1.0 && (s - bs).abs() < 1.0);
+        assert!((l - (bl - 20.0)).abs() < 0.5);
+        assert_eq!(blue.with_alpha(0.5).lighten(0.1).a, 0.5);
+    }
+}
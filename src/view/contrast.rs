@@ -0,0 +1,149 @@
+use std::fmt;
+use std::fmt::Formatter;
+use crate::backend::backend::Node;
+use crate::backend::memory::{MemoryBackend, MemoryNode};
+use crate::view::color::{Colors, Srgb};
+use crate::view::view::{Error, Viewable};
+
+// Tags whose content is typed by the user rather than set as text
+const FIELD_TAGS: [&str; 2] = ["input", "textarea"];
+
+// Level is a WCAG conformance level for the contrast of text
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Level {
+    AA,
+    AAA,
+}
+
+impl Level {
+    // Minimum contrast ratio, large text being at least 24px or 18.66px bold
+    pub fn required(&self, large: bool) -> f32 {
+        match (self, large) {
+            (Level::AA, false) => 4.5,
+            (Level::AA, true) => 3.0,
+            (Level::AAA, false) => 7.0,
+            (Level::AAA, true) => 4.5,
+        }
+    }
+}
+
+// Issue is an element whose text does not stand out enough from its background
+#[derive(Clone, Debug)]
+pub struct Issue {
+    pub tag: String,
+    pub text: Option<String>,
+    pub color: Srgb,
+    pub background: Srgb,
+    pub ratio: f32,
+    pub required: f32,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>", self.tag)?;
+        if let Some(text) = &self.text {
+            write!(f, " \"{}\"", text)?;
+        }
+        write!(f, ": {} on {} has contrast {:.2}:1, needs {}:1",
+               self.color, self.background, self.ratio, self.required)
+    }
+}
+
+// check builds view into memory and reports every element with text whose color has less
+// contrast with its effective background than level requires. Colors and backgrounds are
+// inherited from the closest ancestor that sets them, starting from black on white.
+// Disabled fields are skipped, WCAG does not cover them. It needs no browser, so it can
+// run in unit tests:
+//
+//     let issues = contrast::check(Login::new().body(), Level::AA)?;
+//     assert!(issues.is_empty(), "{:?}", issues);
+//
+// Building moves the listeners and bindings of view onto the memory elements, so view is
+// taken rather than borrowed, and a tree that is shown in a window cannot be checked.
+pub fn check(mut view: Box<dyn Viewable>, level: Level) -> Result<Vec<Issue>, Error> {
+    let backend = MemoryBackend::new();
+    let root = view.build(&backend)?;
+    let root = MemoryNode::of(&root).ok_or(Error::ForeignElement)?;
+
+    let mut issues = Vec::new();
+    walk(root, Srgb::rgb(0, 0, 0), Srgb::rgb(255, 255, 255), level, &mut issues);
+    Ok(issues)
+}
+
+fn walk(node: &MemoryNode, color: Srgb, background: Srgb, level: Level, issues: &mut Vec<Issue>) {
+    let background = match node.style("background").and_then(|v| parse(&v)) {
+        Some(b) => b.over(&background),
+        None => background,
+    };
+    let color = node.style("color").and_then(|v| parse(&v)).unwrap_or(color);
+
+    let tag = node.tag();
+    let text = node.text().filter(|t| !t.trim().is_empty());
+    let field = FIELD_TAGS.contains(&tag.as_str());
+    let disabled = node.attribute("disabled").is_some();
+
+    if (text.is_some() || field) && !disabled {
+        let required = level.required(is_large(node));
+        let ratio = color.contrast(&background);
+        if ratio < required {
+            issues.push(Issue { tag: tag.clone(), text, color, background, ratio, required });
+        }
+    }
+
+    for child in node.children() {
+        if let Some(child) = MemoryNode::of(&child) {
+            walk(child, color, background, level, issues);
+        }
+    }
+}
+
+fn is_large(node: &MemoryNode) -> bool {
+    let size = node.style("font-size")
+        .and_then(|v| v.strip_suffix("px").and_then(|v| v.parse::<f32>().ok()))
+        .unwrap_or(16.0);
+    let bold = match node.style("font-weight").as_deref() {
+        Some("bold") | Some("bolder") => true,
+        Some(weight) => weight.parse::<u32>().is_ok_and(|w| w >= 700),
+        None => false,
+    };
+    size >= 24.0 || (bold && size >= 18.66)
+}
+
+// Reads the color values the built-in styles write
+fn parse(value: &str) -> Option<Srgb> {
+    value.parse::<Colors>().ok()?.to_color()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::index::Login;
+    use crate::view::color::Srgb;
+    use crate::view::contrast::{check, Level};
+    use crate::view::widget::{Button, Text};
+
+    #[test]
+    fn black_on_white_is_the_highest_ratio() {
+        let black = Srgb::rgb(0, 0, 0);
+        let white = Srgb::rgb(255, 255, 255);
+        assert!((black.contrast(&white) - 21.0).abs() < 0.001);
+        assert!((white.contrast(&black) - 21.0).abs() < 0.001);
+        assert!((white.contrast(&white) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn disabled_buttons_fail_aa() {
+        let issues = check(Box::new(Button::disabled("Save")), Level::AA).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].tag, "button");
+        assert!(issues[0].ratio < issues[0].required);
+
+        assert!(check(Box::new(Text::new("Save")), Level::AAA).unwrap().is_empty());
+    }
+
+    #[test]
+    fn the_login_page_passes_aa() {
+        let issues = check(Login::new().body(), Level::AA).unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+}
\ No newline at end of file
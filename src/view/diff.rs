@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use crate::backend::backend::{Backend, Element, Node};
+use crate::backend::memory::{MemoryBackend, MemoryNode};
+use crate::view::stylesheet;
+use crate::view::view::{Error, Viewable};
+
+// Mounted mirrors a rendered widget tree with the elements each widget is shown in
+pub struct Mounted {
+    pub element: Element,
+    pub children: Vec<Mounted>,
+}
+
+impl Mounted {
+    // None if any widget of the tree is not shown, the children would not line up
+    // with the elements of the snapshot then
+    pub fn collect(view: &mut dyn Viewable) -> Option<Mounted> {
+        let element = view.get_html_element()?.clone();
+        let children = view.get_children().into_iter()
+            .map(|child| Mounted::collect(child.as_mut()))
+            .collect::<Option<Vec<Mounted>>>()?;
+        Some(Mounted { element, children })
+    }
+
+    // Hands the elements to a freshly built tree of the same shape
+    pub fn adopt(self, view: &mut dyn Viewable) {
+        view.mount(self.element);
+        for (child, mounted) in view.get_children().into_iter().zip(self.children) {
+            mounted.adopt(child.as_mut());
+        }
+    }
+}
+
+// snapshot renders view into memory, styled like backend, to be compared by a later
+// rerender. Take it before building view with backend, so view ends up mounted there.
+pub fn snapshot(backend: &dyn Backend, view: &mut dyn Viewable) -> Result<Element, Error> {
+    view.build(&MemoryBackend::mirror(backend))
+}
+
+// rerender replaces `previous`, which is currently shown, with `next`. `snapshot` is
+// what `previous` rendered into memory. `next` is rendered into memory as well and
+// compared to it, so only the tags, text, attributes, styles and children that differ
+// are written to the backend. `next` ends up owning the reused elements, `previous`
+// keeps them if rendering fails. Returns the snapshot of `next`.
+pub fn rerender(backend: &dyn Backend, previous: &mut dyn Viewable, snapshot: &Element,
+                next: &mut dyn Viewable) -> Result<Element, Error> {
+    let mounted = Mounted::collect(previous).ok_or(Error::NotMounted)?;
+
+    let before = MemoryNode::of(snapshot).ok_or(Error::ForeignElement)?;
+    let after_root = self::snapshot(backend, next)?;
+    let after = MemoryNode::of(&after_root).ok_or(Error::ForeignElement)?;
+
+    // The root has no parent to be swapped in, so it has to keep its tag
+    if before.tag() != after.tag() {
+        return Err(Error::RootChanged(after.tag()));
+    }
+
+    let mounted = patch(backend, before, after, mounted)?;
+    mounted.adopt(next);
+
+    stylesheet::flush(backend)?;
+    Ok(after_root)
+}
+
+fn patch(backend: &dyn Backend, before: &MemoryNode, after: &MemoryNode, mounted: Mounted)
+         -> Result<Mounted, Error> {
+    let element = mounted.element;
+
+    if before.text() != after.text() {
+        element.set_text(after.text().as_deref().unwrap_or(""));
+    }
+
+    patch_map(before.attributes(), after.attributes(),
+              |name, value| element.set_attribute(name, value),
+              |name| element.remove_attribute(name))?;
+    patch_map(before.styles(), after.styles(),
+              |name, value| element.set_style(name, value),
+              |name| element.remove_style(name))?;
+
+    let before_children = before.children();
+    let mut mounted_children = mounted.children.into_iter();
+    let mut children = Vec::new();
+
+    for (i, after_child) in after.children().iter().enumerate() {
+        let after_child = MemoryNode::of(after_child).ok_or(Error::ForeignElement)?;
+        let before_child = before_children.get(i).and_then(MemoryNode::of);
+
+        let child = match (before_child, mounted_children.next()) {
+            (Some(before_child), Some(m)) if before_child.tag() == after_child.tag() => {
+                patch(backend, before_child, after_child, m)?
+            }
+            (_, Some(m)) => {
+                let fresh = create(backend, after_child)?;
+                element.replace_child(&fresh.element, &m.element)?;
+                fresh
+            }
+            (_, None) => {
+                let fresh = create(backend, after_child)?;
+                element.append_child(&fresh.element)?;
+                fresh
+            }
+        };
+        children.push(child);
+    }
+
+    for m in mounted_children {
+        element.remove_child(&m.element)?;
+    }
+
+    Ok(Mounted { element, children })
+}
+
+fn patch_map(before: BTreeMap<String, String>, after: BTreeMap<String, String>,
+             set: impl Fn(&str, &str) -> Result<(), Error>,
+             remove: impl Fn(&str) -> Result<(), Error>) -> Result<(), Error> {
+    for (name, value) in &after {
+        if before.get(name) != Some(value) {
+            set(name, value)?;
+        }
+    }
+    for name in before.keys() {
+        if !after.contains_key(name) {
+            remove(name)?;
+        }
+    }
+    Ok(())
+}
+
+// create builds backend elements for a memory subtree
+fn create(backend: &dyn Backend, node: &MemoryNode) -> Result<Mounted, Error> {
+    let element = backend.create_element(&node.tag())?;
+    if let Some(text) = node.text() {
+        element.set_text(&text);
+    }
+    for (name, value) in node.attributes() {
+        element.set_attribute(&name, &value)?;
+    }
+    for (name, value) in node.styles() {
+        element.set_style(&name, &value)?;
+    }
+
+    let mut children = Vec::new();
+    for child in node.children() {
+        let child = create(backend, MemoryNode::of(&child).ok_or(Error::ForeignElement)?)?;
+        element.append_child(&child.element)?;
+        children.push(child);
+    }
+    Ok(Mounted { element, children })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use crate::backend::backend::{Element, Node};
+    use crate::backend::memory::{MemoryBackend, MemoryNode};
+    use crate::view::diff::{rerender, snapshot};
+    use crate::view::event::{Event, EventKind};
+    use crate::view::view::{Error, Viewable};
+    use crate::view::widget::{Button, Column, Link, Listenable, Text};
+
+    fn counting_button(label: &'static str, clicks: &Rc<Cell<u32>>) -> Button {
+        let clicks = Rc::clone(clicks);
+        let mut button = Button::new(label);
+        button.on_click(move |_| clicks.set(clicks.get() + 1));
+        button
+    }
+
+    fn show(backend: &MemoryBackend, view: &mut dyn Viewable) -> (Element, Element) {
+        let snapshot = snapshot(backend, view).unwrap();
+        (view.build(backend).unwrap(), snapshot)
+    }
+
+    fn click(element: &Element) {
+        MemoryNode::of(element).unwrap().dispatch(Event::new(EventKind::Click, None, None));
+    }
+
+    #[test]
+    fn patches_the_elements_in_place() {
+        let backend = MemoryBackend::new();
+        let old_clicks = Rc::new(Cell::new(0));
+        let new_clicks = Rc::new(Cell::new(0));
+        let mut previous = Column::new().child(Text::new("Hello")).child(counting_button("Save", &old_clicks));
+        let (root, before) = show(&backend, &mut previous);
+        let children = MemoryNode::of(&root).unwrap().children();
+
+        let mut next = Column::new()
+            .child(Text::new("Bye"))
+            .child(counting_button("Save", &new_clicks))
+            .child(Text::new("Added"));
+        rerender(&backend, &mut previous, &before, &mut next).unwrap();
+
+        let node = MemoryNode::of(&root).unwrap();
+        let patched = node.children();
+        assert_eq!(patched.len(), 3);
+        assert!(Rc::ptr_eq(&patched[0], &children[0]));
+        assert!(Rc::ptr_eq(&patched[1], &children[1]));
+        assert_eq!(MemoryNode::of(&patched[0]).unwrap().text().as_deref(), Some("Bye"));
+        assert_eq!(MemoryNode::of(&patched[2]).unwrap().text().as_deref(), Some("Added"));
+        assert!(Rc::ptr_eq(next.get_html_element().unwrap(), &root));
+
+        drop(previous);
+        click(&patched[1]);
+        assert_eq!(old_clicks.get(), 0);
+        assert_eq!(new_clicks.get(), 1);
+    }
+
+    #[test]
+    fn replaces_and_removes_children() {
+        let backend = MemoryBackend::new();
+        let mut previous = Column::new().child(Text::new("Keep")).child(Button::new("Go")).child(Text::new("Drop"));
+        let (root, before) = show(&backend, &mut previous);
+        let children = MemoryNode::of(&root).unwrap().children();
+
+        let mut next = Column::new().child(Text::new("Keep")).child(Link::new("Go", "/go"));
+        let after = rerender(&backend, &mut previous, &before, &mut next).unwrap();
+
+        let node = MemoryNode::of(&root).unwrap();
+        let patched = node.children();
+        assert_eq!(patched.len(), 2);
+        assert!(Rc::ptr_eq(&patched[0], &children[0]));
+        assert_eq!(MemoryNode::of(&patched[1]).unwrap().tag(), "a");
+        assert_eq!(MemoryNode::of(&patched[1]).unwrap().attribute("href").as_deref(), Some("/go"));
+        assert_eq!(node.to_string(), MemoryNode::of(&after).unwrap().to_string());
+    }
+
+    #[test]
+    fn a_failed_rerender_keeps_the_previous_tree() {
+        let backend = MemoryBackend::new();
+        let clicks = Rc::new(Cell::new(0));
+        let mut previous = counting_button("Save", &clicks);
+        let (root, before) = show(&backend, &mut previous);
+
+        let mut next = Link::new("Save", "/save");
+        assert!(matches!(rerender(&backend, &mut previous, &before, &mut next), Err(Error::RootChanged(_))));
+
+        click(&root);
+        assert_eq!(clicks.get(), 1);
+        assert_eq!(MemoryNode::of(&root).unwrap().tag(), "button");
+    }
+
+    #[test]
+    fn needs_a_mounted_tree() {
+        let backend = MemoryBackend::new();
+        let mut previous = Column::new().child(Text::new("Hello"));
+        let before = snapshot(&backend, &mut Column::new().child(Text::new("Hello"))).unwrap();
+
+        let mut next = Column::new().child(Text::new("Bye"));
+        assert!(matches!(rerender(&backend, &mut previous, &before, &mut next), Err(Error::NotMounted)));
+    }
+}
@@ -0,0 +1,152 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::rc::Rc;
+use crate::backend::backend::Element;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum EventKind {
+    Click,
+    Input,
+    Change,
+    Focus,
+    Blur,
+    KeyDown,
+}
+
+impl EventKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::Click => "click",
+            EventKind::Input => "input",
+            EventKind::Change => "change",
+            EventKind::Focus => "focus",
+            EventKind::Blur => "blur",
+            EventKind::KeyDown => "keydown",
+        }
+    }
+}
+
+// Event is what a callback receives. It only carries the parts of the DOM event
+// that widgets care about, so handlers never depend on a particular backend.
+#[derive(Clone, Debug)]
+pub struct Event {
+    pub kind: EventKind,
+    // Current value of the target for input, change and key events on form fields
+    pub value: Option<String>,
+    // Key name for keydown events, e.g. "Enter"
+    pub key: Option<String>,
+    // Mouse button of click events, 0 is the primary button
+    pub button: Option<i16>,
+    // Modifier keys held down during mouse and key events
+    pub modifiers: Modifiers,
+    prevented: Rc<Cell<bool>>,
+}
+
+impl Event {
+    pub fn new(kind: EventKind, value: Option<String>, key: Option<String>) -> Event {
+        Event { kind, value, key, button: None, modifiers: Modifiers::default(), prevented: Rc::default() }
+    }
+
+    pub fn with_button(mut self, button: i16) -> Event {
+        self.button = Some(button);
+        self
+    }
+
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Event {
+        self.modifiers = modifiers;
+        self
+    }
+
+    // A click with the primary button and no modifier key. Other clicks usually mean
+    // something else to the browser, e.g. opening a link in a new tab.
+    pub fn is_plain_click(&self) -> bool {
+        self.kind == EventKind::Click && self.button.unwrap_or(0) == 0 && !self.modifiers.any()
+    }
+
+    // Stops the browser's default action, e.g. following a link
+    pub fn prevent_default(&self) {
+        self.prevented.set(true);
+    }
+
+    pub fn is_default_prevented(&self) -> bool {
+        self.prevented.get()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+impl Modifiers {
+    pub fn any(&self) -> bool {
+        self.ctrl || self.shift || self.alt || self.meta
+    }
+}
+
+// Subscription is returned by a backend when a handler is attached to an element.
+// Dropping it removes the handler again.
+pub struct Subscription {
+    _guard: Box<dyn Any>,
+}
+
+impl Subscription {
+    pub fn new(guard: impl Any) -> Subscription {
+        Subscription { _guard: Box::new(guard) }
+    }
+}
+
+// Listener keeps a callback and the subscription it is attached with.
+// Dropping a Listener (together with the widget that owns it) detaches it from the element,
+// so closures do not outlive their element.
+pub struct Listener {
+    kind: EventKind,
+    handler: Rc<dyn Fn(&Event)>,
+    attached: Option<Subscription>,
+}
+
+impl Listener {
+    pub fn new(kind: EventKind, handler: Rc<dyn Fn(&Event)>) -> Listener {
+        Listener { kind, handler, attached: None }
+    }
+
+    pub fn kind(&self) -> EventKind {
+        self.kind
+    }
+
+    // Attaches the callback to the element, replacing any previous attachment
+    pub fn attach(&mut self, element: &Element) {
+        self.attached = None;
+        self.attached = Some(element.listen(self.kind, Rc::clone(&self.handler)));
+    }
+
+    pub fn detach(&mut self) {
+        self.attached = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::view::event::{Event, EventKind, Modifiers};
+
+    #[test]
+    fn plain_clicks() {
+        assert!(Event::new(EventKind::Click, None, None).is_plain_click());
+        assert!(Event::new(EventKind::Click, None, None).with_button(0).is_plain_click());
+        assert!(!Event::new(EventKind::Click, None, None).with_button(1).is_plain_click());
+        assert!(!Event::new(EventKind::KeyDown, None, None).is_plain_click());
+
+        for modifiers in [
+            Modifiers { ctrl: true, ..Modifiers::default() },
+            Modifiers { shift: true, ..Modifiers::default() },
+            Modifiers { alt: true, ..Modifiers::default() },
+            Modifiers { meta: true, ..Modifiers::default() },
+        ] {
+            let event = Event::new(EventKind::Click, None, None).with_button(0).with_modifiers(modifiers);
+            assert!(!event.is_plain_click());
+        }
+    }
+}
@@ -1,18 +1,288 @@
 use std::fmt;
 use std::fmt::Formatter;
+use std::str::FromStr;
+use crate::view::style::intern;
+use crate::view::view::Error;
 
-#[derive(Clone)]
+// Font is a CSS font family: a generic family, a named one or a stack of fallbacks
+#[derive(Clone, PartialEq, Debug)]
 pub enum Font {
+    Serif,
     SansSerif,
+    Monospace,
+    Cursive,
+    // The font of the operating system's interface
+    SystemUi,
     Custom(&'static str),
+    // Tried in order, e.g. the brand font, then the system's, then any sans-serif
+    Stack(Vec<Font>),
+}
+
+impl Font {
+    pub fn stack(fonts: Vec<Font>) -> Font {
+        Font::Stack(fonts)
+    }
 }
 
 impl fmt::Display for Font {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
+            Font::Serif => write!(f, "serif"),
             Font::SansSerif => write!(f, "sans-serif"),
-            Font::Custom(s) => write!(f, "{}%", s),
-            _ => write!(f, "Size not found")
+            Font::Monospace => write!(f, "monospace"),
+            Font::Cursive => write!(f, "cursive"),
+            Font::SystemUi => write!(f, "system-ui"),
+            Font::Custom(s) => write!(f, "{}", quote(s)),
+            Font::Stack(fonts) => {
+                let fonts: Vec<String> = fonts.iter().map(|font| font.to_string()).collect();
+                write!(f, "{}", fonts.join(", "))
+            }
+        }
+    }
+}
+
+// Parses a font-family value, e.g. "Inter", system-ui, sans-serif. Several families
+// give a Stack. Names may be quoted, with \ escaping the next character, unquoted ones
+// must be plain words.
+impl FromStr for Font {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Font, Error> {
+        let invalid = || Error::InvalidFont(s.to_string());
+        let mut fonts = Vec::new();
+        let mut rest = s.trim();
+        loop {
+            let (font, after) = family(rest).ok_or_else(invalid)?;
+            fonts.push(font);
+            match after.trim_start().strip_prefix(',') {
+                Some(next) => rest = next.trim_start(),
+                None if after.trim().is_empty() => break,
+                None => return Err(invalid()),
+            }
+        }
+
+        match fonts.len() {
+            1 => Ok(fonts.remove(0)),
+            _ => Ok(Font::Stack(fonts)),
+        }
+    }
+}
+
+// Reads the family at the start of s, returning it and the text after it
+fn family(s: &str) -> Option<(Font, &str)> {
+    if let Some(quote) = s.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let mut name = String::new();
+        let mut chars = s.char_indices().skip(1);
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => name.push(chars.next()?.1),
+                c if c == quote => {
+                    if name.trim().is_empty() {
+                        return None;
+                    }
+                    return Some((Font::Custom(intern(&name)), &s[i + 1..]));
+                }
+                c => name.push(c),
+            }
+        }
+        return None;
+    }
+
+    let end = s.find(',').unwrap_or(s.len());
+    let family = s[..end].trim();
+    let font = match family.to_ascii_lowercase().as_str() {
+        "serif" => Font::Serif,
+        "sans-serif" => Font::SansSerif,
+        "monospace" => Font::Monospace,
+        "cursive" => Font::Cursive,
+        "system-ui" => Font::SystemUi,
+        _ if family.split(' ').all(|word| word.chars().next().is_some_and(|c| c.is_alphabetic())
+            && word.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')) => Font::Custom(intern(family)),
+        _ => return None,
+    };
+    Some((font, &s[end..]))
+}
+
+// Quotes a family name or URL, escaping what would end the string early
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+// FontDisplay is how text is shown while its font loads
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FontDisplay {
+    Auto,
+    // Invisible text for a short while, then the fallback until the font is ready
+    Block,
+    // The fallback right away, swapped for the font when it is ready
+    Swap,
+    // Like Swap, but a font that takes too long is not swapped in anymore
+    Fallback,
+    // The font only if it is ready almost at once, e.g. from the cache
+    Optional,
+}
+
+impl fmt::Display for FontDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FontDisplay::Auto => write!(f, "auto"),
+            FontDisplay::Block => write!(f, "block"),
+            FontDisplay::Swap => write!(f, "swap"),
+            FontDisplay::Fallback => write!(f, "fallback"),
+            FontDisplay::Optional => write!(f, "optional"),
+        }
+    }
+}
+
+// FontFace describes a web font served with the app. Add it to a page with Head::font_face,
+// or load it in the browser with fonts::load to know when it is ready.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FontFace {
+    pub family: &'static str,
+    // Font files, the first one the browser supports is used
+    pub sources: Vec<&'static str>,
+    pub weight: Option<&'static str>,
+    pub style: Option<&'static str>,
+    pub display: FontDisplay,
+}
+
+impl FontFace {
+    pub fn new(family: &'static str, url: &'static str) -> FontFace {
+        FontFace { family, sources: vec![url], weight: None, style: None, display: FontDisplay::Swap }
+    }
+
+    // Another file of the same font, e.g. a woff fallback for a woff2
+    pub fn source(mut self, url: &'static str) -> FontFace {
+        self.sources.push(url);
+        self
+    }
+
+    // e.g. "400", or "100 900" for a variable font
+    pub fn weight(mut self, weight: &'static str) -> FontFace {
+        self.weight = Some(weight);
+        self
+    }
+
+    // e.g. "italic"
+    pub fn style(mut self, style: &'static str) -> FontFace {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn display(mut self, display: FontDisplay) -> FontFace {
+        self.display = display;
+        self
+    }
+
+    // The family to style text with
+    pub fn font(&self) -> Font {
+        Font::Custom(self.family)
+    }
+
+    // Value of the src descriptor, e.g. url("/brand.woff2") format("woff2")
+    pub fn src(&self) -> String {
+        self.sources.iter()
+            .map(|url| match format(url) {
+                Some(format) => format!("url({}) format(\"{}\")", quote(url), format),
+                None => format!("url({})", quote(url)),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    // The @font-face rule
+    pub fn css(&self) -> String {
+        let mut descriptors = vec![
+            format!("font-family: {}", self.font()),
+            format!("src: {}", self.src()),
+        ];
+        if let Some(weight) = self.weight {
+            descriptors.push(format!("font-weight: {}", weight));
+        }
+        if let Some(style) = self.style {
+            descriptors.push(format!("font-style: {}", style));
+        }
+        descriptors.push(format!("font-display: {}", self.display));
+        format!("@font-face {{ {} }}", descriptors.join("; "))
+    }
+}
+
+// Format of a font file, from its extension
+pub fn format(url: &str) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next().unwrap_or("");
+    match path.rsplit('.').next()?.to_ascii_lowercase().as_str() {
+        "woff2" => Some("woff2"),
+        "woff" => Some("woff"),
+        "ttf" => Some("truetype"),
+        "otf" => Some("opentype"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::view::font::{format, Font, FontDisplay, FontFace};
+
+    #[test]
+    fn formats_from_extensions() {
+        assert_eq!(format("/fonts/inter.woff2"), Some("woff2"));
+        assert_eq!(format("/fonts/inter.WOFF"), Some("woff"));
+        assert_eq!(format("https://example.com/inter.ttf?v=2#latin"), Some("truetype"));
+        assert_eq!(format("inter.otf"), Some("opentype"));
+        assert_eq!(format("/fonts/inter.svg"), None);
+        assert_eq!(format("/fonts/inter"), None);
+    }
+
+    #[test]
+    fn writes_font_face_rules() {
+        let face = FontFace::new("Inter", "/fonts/inter.woff2")
+            .source("/fonts/inter.woff")
+            .weight("100 900")
+            .style("italic")
+            .display(FontDisplay::Optional);
+        assert_eq!(face.css(), "@font-face { font-family: \"Inter\"; \
+            src: url(\"/fonts/inter.woff2\") format(\"woff2\"), url(\"/fonts/inter.woff\") format(\"woff\"); \
+            font-weight: 100 900; font-style: italic; font-display: optional }");
+        assert_eq!(FontFace::new("Brand", "/brand").css(),
+                   "@font-face { font-family: \"Brand\"; src: url(\"/brand\"); font-display: swap }");
+    }
+
+    #[test]
+    fn displays_families() {
+        let stack = Font::stack(vec![Font::Custom("Inter"), Font::SystemUi, Font::SansSerif]);
+        assert_eq!(stack.to_string(), "\"Inter\", system-ui, sans-serif");
+        assert_eq!(Font::Custom("Say \"Hi\"").to_string(), "\"Say \\\"Hi\\\"\"");
+        assert_eq!(Font::Custom("C:\\Fonts").to_string(), "\"C:\\\\Fonts\"");
+    }
+
+    #[test]
+    fn parses_families() {
+        assert_eq!("\"Inter\", sans-serif".parse::<Font>().unwrap(),
+                   Font::stack(vec![Font::Custom("Inter"), Font::SansSerif]));
+        assert_eq!("'Open Sans'".parse::<Font>().unwrap(), Font::Custom("Open Sans"));
+        assert_eq!("Open Sans".parse::<Font>().unwrap(), Font::Custom("Open Sans"));
+        assert_eq!("\"Serif\"".parse::<Font>().unwrap(), Font::Custom("Serif"));
+        assert_eq!("\"Acme, Inc\", serif".parse::<Font>().unwrap(),
+                   Font::stack(vec![Font::Custom("Acme, Inc"), Font::Serif]));
+
+        for font in [Font::Custom("Say \"Hi\""), Font::Custom("C:\\Fonts"), Font::Custom("It's")] {
+            assert_eq!(font.to_string().parse::<Font>().unwrap(), font);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_families() {
+        for text in ["", "\"\"", "\"Inter", "Inter\"", "\"Inter\" bold", "Inter,", ", serif", "12px Inter", "Inter;"] {
+            assert!(text.parse::<Font>().is_err(), "{}", text);
         }
     }
 }
\ No newline at end of file
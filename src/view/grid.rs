@@ -0,0 +1,217 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::str::FromStr;
+use crate::view::shadow::split;
+use crate::view::size::Size;
+use crate::view::view::Error;
+
+// Track is the size of one column or row of a Grid
+#[derive(Clone, PartialEq, Debug)]
+pub enum Track {
+    // A length, a percentage, a share of the free space with Size::Fr, or Size::Auto
+    Size(Size),
+    // At least min and at most max, e.g. minmax(200px, 1fr)
+    MinMax(Size, Size),
+    // As small as the content can be without overflowing
+    MinContent,
+    // As large as the content is without wrapping
+    MaxContent,
+}
+
+impl Track {
+    pub fn minmax(min: Size, max: Size) -> Track {
+        Track::MinMax(min, max)
+    }
+}
+
+impl From<Size> for Track {
+    fn from(size: Size) -> Self {
+        Track::Size(size)
+    }
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Track::Size(size) => write!(f, "{}", size),
+            Track::MinMax(min, max) => write!(f, "minmax({}, {})", min, max),
+            Track::MinContent => write!(f, "min-content"),
+            Track::MaxContent => write!(f, "max-content"),
+        }
+    }
+}
+
+impl FromStr for Track {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Track, Error> {
+        let s = s.trim();
+        match s.to_ascii_lowercase().as_str() {
+            "min-content" => return Ok(Track::MinContent),
+            "max-content" => return Ok(Track::MaxContent),
+            _ => {}
+        }
+        if let Some(args) = s.strip_prefix("minmax(").and_then(|args| args.strip_suffix(')')) {
+            return match split(args, ',').as_slice() {
+                [min, max] => Ok(Track::MinMax(min.parse()?, max.parse()?)),
+                _ => Err(Error::InvalidSize(s.to_string())),
+            };
+        }
+        Ok(Track::Size(s.parse()?))
+    }
+}
+
+// Template is the columns or rows of a Grid
+#[derive(Clone, PartialEq, Debug)]
+pub enum Template {
+    // One track after another, e.g. a label column and an input column
+    Tracks(Vec<Track>),
+    // The same track a number of times
+    Repeat(u16, Track),
+    // As many tracks as fit, so a dashboard gets more columns on wider screens.
+    // The track needs a fixed minimum, e.g. Track::minmax(Size::Pixel(240.0), Size::Fr(1.0)).
+    AutoFill(Track),
+    // Like AutoFill, but tracks left empty collapse and the others grow into their space
+    AutoFit(Track),
+}
+
+impl Template {
+    pub fn tracks(tracks: Vec<Track>) -> Template {
+        Template::Tracks(tracks)
+    }
+
+    pub fn repeat(count: u16, track: impl Into<Track>) -> Template {
+        Template::Repeat(count, track.into())
+    }
+
+    pub fn auto_fill(track: impl Into<Track>) -> Template {
+        Template::AutoFill(track.into())
+    }
+
+    pub fn auto_fit(track: impl Into<Track>) -> Template {
+        Template::AutoFit(track.into())
+    }
+}
+
+impl Template {
+    // Without a track the grid has no explicit columns or rows, like "none"
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Template::Tracks(tracks) => tracks.is_empty(),
+            Template::Repeat(count, _) => *count == 0,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "none");
+        }
+        match self {
+            Template::Tracks(tracks) => {
+                let tracks: Vec<String> = tracks.iter().map(|track| track.to_string()).collect();
+                write!(f, "{}", tracks.join(" "))
+            }
+            Template::Repeat(count, track) => write!(f, "repeat({}, {})", count, track),
+            Template::AutoFill(track) => write!(f, "repeat(auto-fill, {})", track),
+            Template::AutoFit(track) => write!(f, "repeat(auto-fit, {})", track),
+        }
+    }
+}
+
+// Parses tracks separated by spaces, or a single repeat() as Display writes it.
+// "none" gives no tracks.
+impl FromStr for Template {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Template, Error> {
+        let s = s.trim();
+        let invalid = || Error::InvalidSize(s.to_string());
+        if s.eq_ignore_ascii_case("none") {
+            return Ok(Template::Tracks(Vec::new()));
+        }
+        if let Some(args) = s.strip_prefix("repeat(").and_then(|args| args.strip_suffix(')')) {
+            let (count, track) = match split(args, ',').as_slice() {
+                [count, track] => (count.to_ascii_lowercase(), track.parse::<Track>()?),
+                _ => return Err(invalid()),
+            };
+            return match count.as_str() {
+                "auto-fill" => Ok(Template::AutoFill(track)),
+                "auto-fit" => Ok(Template::AutoFit(track)),
+                count => match count.parse::<u16>() {
+                    Ok(count) if count > 0 => Ok(Template::Repeat(count, track)),
+                    _ => Err(invalid()),
+                },
+            };
+        }
+
+        let tracks = split(s, ' ').into_iter()
+            .map(|track| track.parse())
+            .collect::<Result<Vec<Track>, Error>>()?;
+        match tracks.is_empty() {
+            true => Err(invalid()),
+            false => Ok(Template::Tracks(tracks)),
+        }
+    }
+}
+
+// Line is where a child of a Grid starts or ends along the columns or rows
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Line {
+    // Placed by the grid, the default
+    Auto,
+    // A line by number, from 1. Negative numbers count from the last line.
+    At(i16),
+    // As many tracks from the other end
+    Span(u16),
+}
+
+impl Line {
+    // There is no line 0 and a span covers at least one track, those lines are Auto
+    pub(crate) fn or_auto(self) -> Line {
+        match self {
+            Line::At(0) | Line::Span(0) => Line::Auto,
+            line => line,
+        }
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Line::Auto => write!(f, "auto"),
+            Line::At(line) => write!(f, "{}", line),
+            Line::Span(tracks) => write!(f, "span {}", tracks),
+        }
+    }
+}
+
+impl FromStr for Line {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Line, Error> {
+        let s = s.trim();
+        let invalid = || Error::InvalidValue("grid line".to_string(), s.to_string());
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(Line::Auto);
+        }
+        match s.strip_prefix("span ") {
+            Some(tracks) => tracks.trim().parse::<u16>().ok()
+                .filter(|tracks| *tracks > 0)
+                .map(Line::Span)
+                .ok_or_else(invalid),
+            None => s.parse::<i16>().ok()
+                .filter(|line| *line != 0)
+                .map(Line::At)
+                .ok_or_else(invalid),
+        }
+    }
+}
+
+// Names a template area is made of cells with, "." leaves a cell out of every area
+pub(crate) fn is_area_name(name: &str) -> bool {
+    name == "." || (name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+}
\ No newline at end of file
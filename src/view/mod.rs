@@ -3,4 +3,13 @@ pub mod size;
 pub mod widget;
 pub mod style;
 pub mod color;
-pub mod font;
\ No newline at end of file
+pub mod font;
+pub mod event;
+pub mod reactive;
+pub mod diff;
+pub mod stylesheet;
+pub mod breakpoint;
+pub mod theme;
+pub mod contrast;
+pub mod shadow;
+pub mod grid;
\ No newline at end of file
@@ -0,0 +1,348 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::{Rc, Weak};
+use crate::backend::backend::Element;
+
+thread_local! {
+    // The effect that is currently running. Signals read while it runs subscribe it.
+    static OBSERVER: RefCell<Option<Rc<EffectState>>> = const { RefCell::new(None) };
+    // Effects to run because a signal they read changed, in the order they were notified
+    static QUEUE: RefCell<VecDeque<Weak<EffectState>>> = RefCell::default();
+    static FLUSHING: Cell<bool> = const { Cell::new(false) };
+}
+
+struct EffectState {
+    run: RefCell<Box<dyn FnMut()>>,
+    // Counts the runs. Subscriptions made by an earlier run are stale, so a signal the
+    // effect stopped reading no longer triggers it.
+    generation: Cell<u64>,
+}
+
+impl EffectState {
+    fn run(state: &Rc<EffectState>) {
+        state.generation.set(state.generation.get() + 1);
+        let previous = OBSERVER.with(|o| o.replace(Some(Rc::clone(state))));
+        (state.run.borrow_mut())();
+        OBSERVER.with(|o| *o.borrow_mut() = previous);
+    }
+}
+
+// Queues effects and runs the queue unless it is already running. An effect that sets
+// a signal, even one it reads itself, only queues the effects of that signal, which
+// run after it has returned.
+fn schedule(effects: Vec<Weak<EffectState>>) {
+    QUEUE.with(|q| {
+        let mut queue = q.borrow_mut();
+        for effect in effects {
+            if !queue.iter().any(|queued| queued.ptr_eq(&effect)) {
+                queue.push_back(effect);
+            }
+        }
+    });
+    if FLUSHING.with(|f| f.replace(true)) {
+        return;
+    }
+
+    while let Some(effect) = QUEUE.with(|q| q.borrow_mut().pop_front()) {
+        // Effects dropped while queued are skipped
+        if let Some(effect) = effect.upgrade() {
+            EffectState::run(&effect);
+        }
+    }
+    FLUSHING.with(|f| f.set(false));
+}
+
+// Effect runs a closure once and again whenever a signal it read changes.
+// The effect stops when it is dropped.
+pub struct Effect {
+    // Signals only hold weak references, so this is what keeps the effect alive
+    _state: Rc<EffectState>,
+}
+
+impl Effect {
+    pub fn new(f: impl FnMut() + 'static) -> Effect {
+        let state = Rc::new(EffectState { run: RefCell::new(Box::new(f)), generation: Cell::new(0) });
+        EffectState::run(&state);
+        Effect { _state: state }
+    }
+}
+
+// An effect that read a signal, in the run it read it in
+struct Subscriber {
+    effect: Weak<EffectState>,
+    generation: u64,
+}
+
+type Write<T> = Box<dyn FnOnce(&mut T)>;
+
+struct SignalState<T> {
+    value: RefCell<T>,
+    // Writes made while the value is borrowed by with, applied once it is released
+    pending: RefCell<Vec<Write<T>>>,
+    subscribers: RefCell<Vec<Subscriber>>,
+}
+
+// Signal is a value that notifies the effects which read it when it changes.
+// Cloning a signal gives another handle to the same value.
+pub struct Signal<T> {
+    state: Rc<SignalState<T>>,
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Signal { state: Rc::clone(&self.state) }
+    }
+}
+
+impl<T: 'static> Signal<T> {
+    pub fn new(value: T) -> Signal<T> {
+        Signal {
+            state: Rc::new(SignalState {
+                value: RefCell::new(value),
+                pending: RefCell::default(),
+                subscribers: RefCell::default(),
+            })
+        }
+    }
+
+    // Reads the value through f and subscribes the running effect, if any.
+    // The signal may be set inside f, the new value is stored once f returns.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        self.track();
+        let result = f(&self.state.value.borrow());
+        self.apply_pending();
+        result
+    }
+
+    pub fn set(&self, value: T) {
+        self.update(move |v| *v = value);
+    }
+
+    pub fn update(&self, f: impl FnOnce(&mut T) + 'static) {
+        self.state.pending.borrow_mut().push(Box::new(f));
+        self.apply_pending();
+    }
+
+    fn apply_pending(&self) {
+        let changed = {
+            // Still borrowed by an outer with, which applies the writes when it returns
+            let Ok(mut value) = self.state.value.try_borrow_mut() else { return };
+            let pending: Vec<_> = self.state.pending.borrow_mut().drain(..).collect();
+            let changed = !pending.is_empty();
+            for write in pending {
+                write(&mut value);
+            }
+            changed
+        };
+        if changed {
+            self.notify();
+        }
+    }
+
+    fn track(&self) {
+        OBSERVER.with(|o| {
+            if let Some(effect) = o.borrow().as_ref() {
+                let generation = effect.generation.get();
+                let mut subscribers = self.state.subscribers.borrow_mut();
+                match subscribers.iter_mut().find(|s| s.effect.as_ptr() == Rc::as_ptr(effect)) {
+                    Some(subscriber) => subscriber.generation = generation,
+                    None => subscribers.push(Subscriber { effect: Rc::downgrade(effect), generation }),
+                }
+            }
+        });
+    }
+
+    fn notify(&self) {
+        let effects: Vec<Weak<EffectState>> = {
+            let mut subscribers = self.state.subscribers.borrow_mut();
+            subscribers.retain(|s| s.effect.upgrade().is_some_and(|e| e.generation.get() == s.generation));
+            subscribers.iter().map(|s| Weak::clone(&s.effect)).collect()
+        };
+        schedule(effects);
+    }
+}
+
+impl<T: Clone + 'static> Signal<T> {
+    pub fn get(&self) -> T {
+        self.with(|v| v.clone())
+    }
+}
+
+// Derived is a read-only signal computed from other signals. It recomputes when they change.
+pub struct Derived<T> {
+    signal: Signal<T>,
+    _effect: Rc<Effect>,
+}
+
+impl<T> Clone for Derived<T> {
+    fn clone(&self) -> Self {
+        Derived { signal: self.signal.clone(), _effect: Rc::clone(&self._effect) }
+    }
+}
+
+impl<T: 'static> Derived<T> {
+    pub fn new(f: impl Fn() -> T + 'static) -> Derived<T> {
+        let slot: Rc<RefCell<Option<Signal<T>>>> = Rc::default();
+        let target = Rc::clone(&slot);
+        let effect = Effect::new(move || {
+            let value = f();
+            let signal = target.borrow().clone();
+            match signal {
+                Some(signal) => signal.set(value),
+                None => *target.borrow_mut() = Some(Signal::new(value)),
+            }
+        });
+        let signal = slot.borrow().clone().unwrap();
+        Derived { signal, _effect: Rc::new(effect) }
+    }
+
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        self.signal.with(f)
+    }
+}
+
+impl<T: Clone + 'static> Derived<T> {
+    pub fn get(&self) -> T {
+        self.signal.get()
+    }
+}
+
+// Binding applies a property to an element inside an effect,
+// so the property follows the signals it reads.
+pub struct Binding {
+    apply: Rc<dyn Fn(&Element)>,
+    effect: Option<Effect>,
+}
+
+impl Binding {
+    pub fn new(apply: Rc<dyn Fn(&Element)>) -> Binding {
+        Binding { apply, effect: None }
+    }
+
+    // Attaches the binding to the element, replacing any previous attachment
+    pub fn attach(&mut self, element: &Element) {
+        self.effect = None;
+        let element = Rc::clone(element);
+        let apply = Rc::clone(&self.apply);
+        self.effect = Some(Effect::new(move || apply(&element)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use crate::backend::backend::Backend;
+    use crate::backend::memory::{MemoryBackend, MemoryNode};
+    use crate::view::reactive::{Binding, Derived, Effect, Signal};
+
+    #[test]
+    fn set_reruns_effects_that_read_the_signal() {
+        let count = Signal::new(1);
+        let seen = Rc::new(Cell::new(0));
+        let _effect = Effect::new({
+            let (count, seen) = (count.clone(), Rc::clone(&seen));
+            move || seen.set(count.get())
+        });
+        assert_eq!(seen.get(), 1);
+
+        count.set(2);
+        assert_eq!(seen.get(), 2);
+    }
+
+    #[test]
+    fn binding_updates_a_memory_element() {
+        let backend = MemoryBackend::new();
+        let element = backend.create_element("p").unwrap();
+        let name = Signal::new("Ada".to_string());
+        let mut binding = Binding::new(Rc::new({
+            let name = name.clone();
+            move |e| e.set_text(&format!("Hello {}", name.get()))
+        }));
+        binding.attach(&element);
+        assert_eq!(MemoryNode::of(&element).unwrap().text().as_deref(), Some("Hello Ada"));
+
+        name.set("Grace".to_string());
+        assert_eq!(MemoryNode::of(&element).unwrap().text().as_deref(), Some("Hello Grace"));
+    }
+
+    #[test]
+    fn derived_follows_its_sources() {
+        let width = Signal::new(2);
+        let height = Signal::new(3);
+        let area = Derived::new({
+            let (width, height) = (width.clone(), height.clone());
+            move || width.get() * height.get()
+        });
+        assert_eq!(area.get(), 6);
+
+        width.set(4);
+        assert_eq!(area.get(), 12);
+        height.set(5);
+        assert_eq!(area.get(), 20);
+    }
+
+    #[test]
+    fn effect_may_write_a_signal_it_reads() {
+        let count = Signal::new(5);
+        let runs = Rc::new(Cell::new(0));
+        // Clamps the counter to 10
+        let _effect = Effect::new({
+            let (count, runs) = (count.clone(), Rc::clone(&runs));
+            move || {
+                runs.set(runs.get() + 1);
+                if count.get() > 10 {
+                    count.set(10);
+                }
+            }
+        });
+
+        count.set(15);
+        assert_eq!(count.get(), 10);
+        assert_eq!(runs.get(), 3);
+    }
+
+    #[test]
+    fn signal_may_be_set_while_it_is_read() {
+        let count = Signal::new(1);
+        count.with(|value| count.set(value + 1));
+        assert_eq!(count.get(), 2);
+    }
+
+    #[test]
+    fn dropped_effect_stops_running() {
+        let count = Signal::new(0);
+        let runs = Rc::new(Cell::new(0));
+        let effect = Effect::new({
+            let (count, runs) = (count.clone(), Rc::clone(&runs));
+            move || {
+                count.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+        count.set(1);
+        assert_eq!(runs.get(), 2);
+
+        drop(effect);
+        count.set(2);
+        assert_eq!(runs.get(), 2);
+    }
+
+    #[test]
+    fn signals_no_longer_read_do_not_rerun_an_effect() {
+        let show = Signal::new(true);
+        let name = Signal::new("Ada");
+        let texts = Rc::new(RefCell::new(Vec::new()));
+        let _effect = Effect::new({
+            let (show, name, texts) = (show.clone(), name.clone(), Rc::clone(&texts));
+            move || {
+                let text = if show.get() { name.get() } else { "hidden" };
+                texts.borrow_mut().push(text);
+            }
+        });
+
+        show.set(false);
+        name.set("Grace");
+        assert_eq!(*texts.borrow(), vec!["Ada", "hidden"]);
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::str::FromStr;
+use crate::view::color::Colors;
+use crate::view::size::Size;
+use crate::view::view::Error;
+
+// Shadow is one shadow of a box, offset from it and blurred
+#[derive(Clone, PartialEq, Debug)]
+pub struct Shadow {
+    pub x: Size,
+    pub y: Size,
+    pub blur: Size,
+    // Grows the shadow before it is blurred, or shrinks it when negative
+    pub spread: Size,
+    pub color: Colors,
+    // Drawn inside the box, as if it were sunk into the page
+    pub inset: bool,
+}
+
+impl Shadow {
+    pub fn new(x: Size, y: Size, blur: Size, color: impl Into<Colors>) -> Shadow {
+        Shadow { x, y, blur, spread: Size::Pixel(0.0), color: color.into(), inset: false }
+    }
+
+    pub fn spread(mut self, spread: Size) -> Shadow {
+        self.spread = spread;
+        self
+    }
+
+    pub fn inset(mut self) -> Shadow {
+        self.inset = true;
+        self
+    }
+}
+
+impl fmt::Display for Shadow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.inset {
+            write!(f, "inset ")?;
+        }
+        write!(f, "{} {} {} {} {}", self.x, self.y, self.blur, self.spread, self.color)
+    }
+}
+
+// Parses one shadow as Display writes it: "inset" first if it is one, then two to four
+// lengths and the color. Blur and spread default to 0.
+impl FromStr for Shadow {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Shadow, Error> {
+        let invalid = || Error::InvalidValue("box-shadow".to_string(), s.trim().to_string());
+        let mut parts = split(s, ' ');
+        let inset = parts.first().is_some_and(|p| p.eq_ignore_ascii_case("inset"));
+        if inset {
+            parts.remove(0);
+        }
+        let color = parts.pop().ok_or_else(invalid)?.parse::<Colors>()?;
+        let sizes = parts.iter().map(|p| p.parse::<Size>()).collect::<Result<Vec<Size>, Error>>()?;
+
+        let zero = || Size::Pixel(0.0);
+        let (x, y, blur, spread) = match sizes.as_slice() {
+            [x, y] => (x.clone(), y.clone(), zero(), zero()),
+            [x, y, blur] => (x.clone(), y.clone(), blur.clone(), zero()),
+            [x, y, blur, spread] => (x.clone(), y.clone(), blur.clone(), spread.clone()),
+            _ => return Err(invalid()),
+        };
+        Ok(Shadow { x, y, blur, spread, color, inset })
+    }
+}
+
+// Splits text on separator where it is not inside parentheses, e.g. the shadows of a
+// box-shadow value whose colors are rgba() with commas of their own
+pub(crate) fn split(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts.into_iter().map(str::trim).filter(|p| !p.is_empty()).collect()
+}
\ No newline at end of file
@@ -1,41 +1,544 @@
 use std::fmt;
 use std::fmt::Formatter;
+use std::ops;
+use std::str::FromStr;
+use crate::view::view::Error;
 
-#[derive(Clone)]
-pub struct Edge {
-    pub top: Size,
-    pub right: Size,
-    pub left: Size,
-    pub bottom: Size,
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+// Edge holds a value per side of a box, e.g. the padding of each side.
+// Sides left out are not set.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Edge<T = Size> {
+    pub top: Option<T>,
+    pub right: Option<T>,
+    pub bottom: Option<T>,
+    pub left: Option<T>,
 }
 
 impl Edge {
+    // Zero on every side
     pub fn new() -> Edge {
+        Edge::all(Size::Pixel(0.0))
+    }
+}
+
+impl<T: Clone> Edge<T> {
+    pub fn all(value: T) -> Edge<T> {
+        Edge::symmetric(value.clone(), value)
+    }
+
+    // vertical on top and bottom, horizontal on left and right
+    pub fn symmetric(vertical: T, horizontal: T) -> Edge<T> {
         Edge {
-            top: Size::Pixel(0.0),
-            right: Size::Pixel(0.0),
-            left: Size::Pixel(0.0),
-            bottom: Size::Pixel(0.0),
+            top: Some(vertical.clone()),
+            right: Some(horizontal.clone()),
+            bottom: Some(vertical),
+            left: Some(horizontal),
         }
     }
 }
 
-#[derive(Clone)]
+impl<T> Edge<T> {
+    // Only side is set. Chain top, right, bottom and left to set more.
+    pub fn only(side: Side, value: T) -> Edge<T> {
+        Edge { top: None, right: None, bottom: None, left: None }.side(side, value)
+    }
+
+    pub fn side(mut self, side: Side, value: T) -> Edge<T> {
+        match side {
+            Side::Top => self.top = Some(value),
+            Side::Right => self.right = Some(value),
+            Side::Bottom => self.bottom = Some(value),
+            Side::Left => self.left = Some(value),
+        }
+        self
+    }
+
+    pub fn top(self, value: T) -> Edge<T> {
+        self.side(Side::Top, value)
+    }
+
+    pub fn right(self, value: T) -> Edge<T> {
+        self.side(Side::Right, value)
+    }
+
+    pub fn bottom(self, value: T) -> Edge<T> {
+        self.side(Side::Bottom, value)
+    }
+
+    pub fn left(self, value: T) -> Edge<T> {
+        self.side(Side::Left, value)
+    }
+
+    // Sides in CSS order: top, right, bottom, left
+    pub fn sides(&self) -> [Option<&T>; 4] {
+        [self.top.as_ref(), self.right.as_ref(), self.bottom.as_ref(), self.left.as_ref()]
+    }
+}
+
+impl Default for Edge {
+    fn default() -> Self {
+        Edge::new()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomRight,
+    BottomLeft,
+}
+
+// Corners holds a value per corner of a box, e.g. its border radius.
+// Corners left out are not set.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Corners<T = Size> {
+    pub top_left: Option<T>,
+    pub top_right: Option<T>,
+    pub bottom_right: Option<T>,
+    pub bottom_left: Option<T>,
+}
+
+impl<T: Clone> Corners<T> {
+    pub fn all(value: T) -> Corners<T> {
+        Corners {
+            top_left: Some(value.clone()),
+            top_right: Some(value.clone()),
+            bottom_right: Some(value.clone()),
+            bottom_left: Some(value),
+        }
+    }
+
+    // Both corners of the top side, and so on for the other sides
+    pub fn top(self, value: T) -> Corners<T> {
+        self.corner(Corner::TopLeft, value.clone()).corner(Corner::TopRight, value)
+    }
+
+    pub fn bottom(self, value: T) -> Corners<T> {
+        self.corner(Corner::BottomLeft, value.clone()).corner(Corner::BottomRight, value)
+    }
+
+    pub fn left(self, value: T) -> Corners<T> {
+        self.corner(Corner::TopLeft, value.clone()).corner(Corner::BottomLeft, value)
+    }
+
+    pub fn right(self, value: T) -> Corners<T> {
+        self.corner(Corner::TopRight, value.clone()).corner(Corner::BottomRight, value)
+    }
+}
+
+impl<T> Corners<T> {
+    // Only corner is set. Chain corner to set more.
+    pub fn only(corner: Corner, value: T) -> Corners<T> {
+        Corners { top_left: None, top_right: None, bottom_right: None, bottom_left: None }
+            .corner(corner, value)
+    }
+
+    pub fn corner(mut self, corner: Corner, value: T) -> Corners<T> {
+        match corner {
+            Corner::TopLeft => self.top_left = Some(value),
+            Corner::TopRight => self.top_right = Some(value),
+            Corner::BottomRight => self.bottom_right = Some(value),
+            Corner::BottomLeft => self.bottom_left = Some(value),
+        }
+        self
+    }
+
+    // Corners in CSS order: top left, top right, bottom right, bottom left
+    pub fn corners(&self) -> [Option<&T>; 4] {
+        [self.top_left.as_ref(), self.top_right.as_ref(), self.bottom_right.as_ref(), self.bottom_left.as_ref()]
+    }
+}
+
+// Size is a CSS length. Sizes combine with +, -, * and / into calc() expressions,
+// e.g. Size::Rem(1.0) + Size::Vw(2.0) is calc(1rem + 2vw). Math on auto, fit-content or
+// fr is invalid CSS, which a browser ignores, so such an expression is auto instead.
+#[derive(Clone, PartialEq, Debug)]
 pub enum Size {
     MatchParent,
     WrapContent,
+    Auto,
     Pixel(f32),
-    Percent(i32),
+    Percent(f32),
+    // Relative to the font size of the element
+    Em(f32),
+    // Relative to the font size of the root element
+    Rem(f32),
+    // Percent of the viewport width and height
+    Vw(f32),
+    Vh(f32),
+    // Share of the free space in a grid track
+    Fr(f32),
+    Sum(Box<Size>, Box<Size>),
+    Difference(Box<Size>, Box<Size>),
+    Product(Box<Size>, f32),
+    Quotient(Box<Size>, f32),
+    Min(Vec<Size>),
+    Max(Vec<Size>),
+    Clamp(Box<Size>, Box<Size>, Box<Size>),
+}
+
+impl Size {
+    // The smallest of sizes
+    pub fn min(sizes: Vec<Size>) -> Size {
+        match !sizes.is_empty() && sizes.iter().all(Size::is_length) {
+            true => Size::Min(sizes),
+            false => Size::Auto,
+        }
+    }
+
+    // The largest of sizes
+    pub fn max(sizes: Vec<Size>) -> Size {
+        match !sizes.is_empty() && sizes.iter().all(Size::is_length) {
+            true => Size::Max(sizes),
+            false => Size::Auto,
+        }
+    }
+
+    // preferred, but no smaller than min and no larger than max,
+    // e.g. clamp(Size::Rem(1.0), Size::Vw(2.5), Size::Rem(2.0)) for fluid type
+    pub fn clamp(min: Size, preferred: Size, max: Size) -> Size {
+        match min.is_length() && preferred.is_length() && max.is_length() {
+            true => Size::Clamp(Box::new(min), Box::new(preferred), Box::new(max)),
+            false => Size::Auto,
+        }
+    }
+
+    // Whether the size can be used in calc(), min(), max() and clamp()
+    pub fn is_length(&self) -> bool {
+        !matches!(self, Size::Auto | Size::WrapContent | Size::Fr(_))
+    }
+
+    fn is_arithmetic(&self) -> bool {
+        matches!(self, Size::Sum(..) | Size::Difference(..) | Size::Product(..) | Size::Quotient(..))
+    }
+
+    // The size as it is written inside calc() and the math functions
+    fn expression(&self) -> String {
+        match self {
+            Size::Sum(a, b) => format!("{} + {}", a.left_operand(), b.operand()),
+            Size::Difference(a, b) => format!("{} - {}", a.left_operand(), b.operand()),
+            Size::Product(a, k) => format!("{} * {}", a.operand(), k),
+            Size::Quotient(a, k) => format!("{} / {}", a.operand(), k),
+            _ => self.to_string(),
+        }
+    }
+
+    // Sums and differences read left to right, so they need no parentheses on the left of one
+    fn left_operand(&self) -> String {
+        match self {
+            Size::Sum(..) | Size::Difference(..) => self.expression(),
+            _ => self.operand(),
+        }
+    }
+
+    fn operand(&self) -> String {
+        if self.is_arithmetic() {
+            format!("({})", self.expression())
+        } else {
+            self.expression()
+        }
+    }
+}
+
+impl ops::Add for Size {
+    type Output = Size;
+
+    fn add(self, other: Size) -> Size {
+        match self.is_length() && other.is_length() {
+            true => Size::Sum(Box::new(self), Box::new(other)),
+            false => Size::Auto,
+        }
+    }
+}
+
+impl ops::Sub for Size {
+    type Output = Size;
+
+    fn sub(self, other: Size) -> Size {
+        match self.is_length() && other.is_length() {
+            true => Size::Difference(Box::new(self), Box::new(other)),
+            false => Size::Auto,
+        }
+    }
+}
+
+impl ops::Mul<f32> for Size {
+    type Output = Size;
+
+    fn mul(self, factor: f32) -> Size {
+        match self.is_length() && factor.is_finite() {
+            true => Size::Product(Box::new(self), factor),
+            false => Size::Auto,
+        }
+    }
+}
+
+impl ops::Div<f32> for Size {
+    type Output = Size;
+
+    fn div(self, divisor: f32) -> Size {
+        match self.is_length() && divisor.is_finite() && divisor != 0.0 {
+            true => Size::Quotient(Box::new(self), divisor),
+            false => Size::Auto,
+        }
+    }
 }
 
 impl fmt::Display for Size {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let list = |sizes: &Vec<Size>| sizes.iter()
+            .map(|s| s.expression())
+            .collect::<Vec<String>>()
+            .join(", ");
+
         match self {
             Size::MatchParent => write!(f, "100%"),
             Size::WrapContent => write!(f, "fit-content"),
+            Size::Auto => write!(f, "auto"),
             Size::Pixel(p) => write!(f, "{}px", p),
             Size::Percent(p) => write!(f, "{}%", p),
-            _ => write!(f, "Size not found")
+            Size::Em(e) => write!(f, "{}em", e),
+            Size::Rem(r) => write!(f, "{}rem", r),
+            Size::Vw(v) => write!(f, "{}vw", v),
+            Size::Vh(v) => write!(f, "{}vh", v),
+            Size::Fr(fr) => write!(f, "{}fr", fr),
+            Size::Sum(..) | Size::Difference(..) | Size::Product(..) | Size::Quotient(..) => {
+                write!(f, "calc({})", self.expression())
+            }
+            Size::Min(sizes) => write!(f, "min({})", list(sizes)),
+            Size::Max(sizes) => write!(f, "max({})", list(sizes)),
+            Size::Clamp(min, preferred, max) => {
+                write!(f, "clamp({}, {}, {})", min.expression(), preferred.expression(), max.expression())
+            }
+        }
+    }
+}
+
+// Parses what Display writes: lengths like "4px" or "1.5rem", "auto", "fit-content",
+// and calc(), min(), max() and clamp() expressions of them
+impl FromStr for Size {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Size, Error> {
+        let mut parser = Parser { text: s.trim(), position: 0 };
+        let size = parser.size()?;
+        match parser.rest().is_empty() {
+            true => Ok(size),
+            false => Err(Error::InvalidSize(s.to_string())),
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    fn error(&self) -> Error {
+        Error::InvalidSize(self.text.to_string())
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), Error> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.error()),
         }
     }
-}
\ No newline at end of file
+
+    fn size(&mut self) -> Result<Size, Error> {
+        self.skip_whitespace();
+        if self.eat("auto") {
+            return Ok(Size::Auto);
+        }
+        if self.eat("fit-content") {
+            return Ok(Size::WrapContent);
+        }
+        if self.eat("calc(") {
+            let size = self.expression()?;
+            self.expect(")")?;
+            return Ok(size);
+        }
+        if self.eat("min(") {
+            return Ok(Size::min(self.list()?));
+        }
+        if self.eat("max(") {
+            return Ok(Size::max(self.list()?));
+        }
+        if self.eat("clamp(") {
+            let mut sizes = self.list()?;
+            if sizes.len() != 3 {
+                return Err(self.error());
+            }
+            let max = sizes.pop().unwrap();
+            let preferred = sizes.pop().unwrap();
+            let min = sizes.pop().unwrap();
+            return Ok(Size::clamp(min, preferred, max));
+        }
+        self.dimension()
+    }
+
+    // Comma separated expressions up to the closing parenthesis
+    fn list(&mut self) -> Result<Vec<Size>, Error> {
+        let mut sizes = vec![self.expression()?];
+        while self.eat(",") {
+            sizes.push(self.expression()?);
+        }
+        self.expect(")")?;
+        Ok(sizes)
+    }
+
+    fn expression(&mut self) -> Result<Size, Error> {
+        let mut size = self.product()?;
+        loop {
+            // Operators need whitespace around them, "-" without it belongs to a number
+            if self.eat("+ ") {
+                size = size + self.product()?;
+            } else if self.eat("- ") {
+                size = size - self.product()?;
+            } else {
+                return Ok(size);
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<Size, Error> {
+        let mut size = self.operand()?;
+        loop {
+            if self.eat("*") {
+                size = size * self.number()?;
+            } else if self.eat("/") {
+                let divisor = self.number()?;
+                if divisor == 0.0 {
+                    return Err(self.error());
+                }
+                size = size / divisor;
+            } else {
+                return Ok(size);
+            }
+        }
+    }
+
+    fn operand(&mut self) -> Result<Size, Error> {
+        if self.eat("(") {
+            let size = self.expression()?;
+            self.expect(")")?;
+            return Ok(size);
+        }
+        match self.size()? {
+            size if size.is_length() => Ok(size),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn number(&mut self) -> Result<f32, Error> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let length = rest.char_indices()
+            .take_while(|(i, c)| c.is_ascii_digit() || *c == '.' || (*i == 0 && (*c == '-' || *c == '+')))
+            .count();
+        let number = rest[..length].parse::<f32>().map_err(|_| self.error())?;
+        self.position += length;
+        Ok(number)
+    }
+
+    fn dimension(&mut self) -> Result<Size, Error> {
+        let number = self.number()?;
+        let rest = self.rest();
+        let length = rest.chars().take_while(|c| c.is_ascii_alphabetic() || *c == '%').count();
+        let size = match &rest[..length] {
+            "px" => Size::Pixel(number),
+            "%" => Size::Percent(number),
+            "em" => Size::Em(number),
+            "rem" => Size::Rem(number),
+            "vw" => Size::Vw(number),
+            "vh" => Size::Vh(number),
+            "fr" => Size::Fr(number),
+            // Zero is the only length that may leave out its unit
+            "" if number == 0.0 => Size::Pixel(0.0),
+            _ => return Err(self.error()),
+        };
+        self.position += length;
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::view::size::Size;
+
+    #[test]
+    fn displays_composed_expressions() {
+        let fluid = Size::Rem(1.0) + Size::Vw(2.0);
+        assert_eq!(fluid.to_string(), "calc(1rem + 2vw)");
+        assert_eq!((fluid.clone() + Size::Pixel(3.0)).to_string(), "calc(1rem + 2vw + 3px)");
+        assert_eq!((Size::Pixel(10.0) - fluid.clone()).to_string(), "calc(10px - (1rem + 2vw))");
+        assert_eq!((fluid.clone() * 2.0).to_string(), "calc((1rem + 2vw) * 2)");
+        assert_eq!((Size::Percent(100.0) / 3.0 - Size::Pixel(8.0)).to_string(), "calc((100% / 3) - 8px)");
+
+        let column = Size::min(vec![Size::MatchParent, Size::Pixel(600.0)]);
+        assert_eq!(column.to_string(), "min(100%, 600px)");
+        assert_eq!((column + Size::Em(1.0)).to_string(), "calc(min(100%, 600px) + 1em)");
+        assert_eq!(Size::max(vec![Size::Vh(50.0), fluid.clone()]).to_string(), "max(50vh, 1rem + 2vw)");
+        assert_eq!(Size::clamp(Size::Rem(1.0), Size::Vw(2.5) + Size::Rem(0.5), Size::Rem(2.0)).to_string(),
+                   "clamp(1rem, 2.5vw + 0.5rem, 2rem)");
+    }
+
+    #[test]
+    fn parses_what_it_displays() {
+        let sizes = [
+            Size::Rem(1.0) + Size::Pixel(4.0),
+            Size::Pixel(10.0) - (Size::Rem(1.0) + Size::Vw(2.0)) * 0.5,
+            Size::min(vec![Size::Percent(100.0), Size::Pixel(600.0) - Size::Em(2.0)]),
+            Size::clamp(Size::Rem(1.0), Size::Vw(2.5), Size::Rem(2.0)),
+        ];
+        for size in sizes {
+            assert_eq!(size.to_string().parse::<Size>().unwrap(), size);
+        }
+        assert_eq!("calc(1rem + 4px)".parse::<Size>().unwrap(), Size::Rem(1.0) + Size::Pixel(4.0));
+    }
+
+    #[test]
+    fn math_needs_lengths() {
+        assert_eq!(Size::Auto + Size::Pixel(1.0), Size::Auto);
+        assert_eq!(Size::Pixel(1.0) - Size::WrapContent, Size::Auto);
+        assert_eq!(Size::Fr(1.0) * 2.0, Size::Auto);
+        assert_eq!(Size::Pixel(1.0) / 0.0, Size::Auto);
+        assert_eq!(Size::min(vec![Size::Fr(1.0), Size::Pixel(200.0)]), Size::Auto);
+        assert_eq!(Size::max(Vec::new()), Size::Auto);
+        assert_eq!(Size::clamp(Size::Pixel(1.0), Size::Auto, Size::Pixel(2.0)), Size::Auto);
+
+        for text in ["calc(auto + 1px)", "min(1fr, 200px)", "max(fit-content, 1px)", "calc(1px / 0)",
+                     "clamp(1px, 2px)", "calc(1px +)", "calc(1px + 2px", "1 px"] {
+            assert!(text.parse::<Size>().is_err(), "{}", text);
+        }
+    }
+}
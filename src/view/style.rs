@@ -1,19 +1,74 @@
-use crate::view::{font, size, color, view};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use crate::backend::backend::Element;
+use crate::view::{font, size, color, grid, shadow, view};
+use crate::view::breakpoint::Breakpoint;
 
 pub trait Style {
     fn name(&self) -> &'static str where Self: Sized;
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error>;
+
+    // CSS properties and values the style sets
+    fn declarations(&self) -> Vec<(&'static str, String)>;
+
+    fn build(&self, element: &Element) -> Result<(), view::Error> {
+        for (name, value) in self.declarations() {
+            element.set_style(name, &value)?;
+        }
+        Ok(())
+    }
+}
+
+const TEXT_DECORATIONS: [&str; 4] = ["none", "underline", "overline", "line-through"];
+
+const TEXT_TRANSFORMS: [&str; 6] = ["none", "capitalize", "uppercase", "lowercase", "initial", "inherit"];
+
+// Values of border-style and outline
+const LINE_STYLES: [&str; 10] = ["none", "hidden", "dotted", "dashed", "solid", "double", "groove", "ridge",
+    "inset", "outset"];
+
+const CURSORS: [&str; 12] = ["auto", "default", "pointer", "text", "move", "not-allowed", "wait", "progress",
+    "help", "crosshair", "grab", "grabbing"];
+
+// State of an element a style can be limited to, see Styleable::style_on
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum State {
+    Hover,
+    Focus,
+    FocusVisible,
+    Active,
+    Disabled,
+}
+
+impl State {
+    pub fn selector(&self) -> &'static str {
+        match self {
+            State::Hover => ":hover",
+            State::Focus => ":focus",
+            State::FocusVisible => ":focus-visible",
+            State::Active => ":active",
+            State::Disabled => ":disabled",
+        }
+    }
+}
+
+// Scope limits a style to a state or to viewports from a breakpoint on
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Scope {
+    State(State),
+    Breakpoint(Breakpoint),
 }
 
 #[derive(Clone)]
 pub struct Custom {
     name: &'static str,
-    val: &'static str,
+    val: String,
 }
 
 impl Custom {
     pub fn new(name: &'static str, val: &'static str) -> Custom {
-        Custom { name, val }
+        Custom { name, val: val.to_string() }
     }
 }
 
@@ -22,9 +77,24 @@ impl Style for Custom {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val.to_string());
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.clone())]
+    }
+}
+
+// Any single property, only checked to be a well-formed declaration
+impl FromStr for Custom {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Custom, view::Error> {
+        let (name, val) = parse_declaration(s)?;
+        // Words joined by dashes, custom properties start with two
+        let word = name.trim_start_matches('-');
+        if !word.starts_with(|c: char| c.is_ascii_alphabetic())
+            || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(view::Error::InvalidDeclaration(s.trim().to_string()));
+        }
+        Ok(Custom { name: intern(&name), val })
     }
 }
 
@@ -39,6 +109,10 @@ impl Width {
         Width { name: "width", val: size }
     }
 
+    pub fn min(size: size::Size) -> Width {
+        Width { name: "min-width", val: size }
+    }
+
     pub fn max(size: size::Size) -> Width {
         Width { name: "max-width", val: size }
     }
@@ -49,9 +123,17 @@ impl Style for Width {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val.to_string());
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for Width {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Width, view::Error> {
+        let (name, val) = parse_property(s, &["width", "min-width", "max-width"])?;
+        Ok(Width { name, val: val.parse()? })
     }
 }
 
@@ -66,6 +148,10 @@ impl Height {
         Height { name: "height", val: size }
     }
 
+    pub fn min(size: size::Size) -> Height {
+        Height { name: "min-height", val: size }
+    }
+
     pub fn max(size: size::Size) -> Height {
         Height { name: "max-height", val: size }
     }
@@ -76,9 +162,17 @@ impl Style for Height {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val.to_string());
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for Height {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Height, view::Error> {
+        let (name, val) = parse_property(s, &["height", "min-height", "max-height"])?;
+        Ok(Height { name, val: val.parse()? })
     }
 }
 
@@ -88,9 +182,9 @@ pub struct Color {
 }
 
 impl Color {
-    pub fn new(color: color::Colors) -> Color {
+    pub fn new(color: impl Into<color::Colors>) -> Color {
         Color {
-            color,
+            color: color.into(),
         }
     }
 }
@@ -100,12 +194,19 @@ impl Style for Color {
         return "COLOR";
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property("color", &self.color.to_string());
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![("color", self.color.to_string())]
     }
 }
 
+impl FromStr for Color {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Color, view::Error> {
+        let (_, color) = parse_property(s, &["color"])?;
+        Ok(Color { color: color.parse()? })
+    }
+}
 
 #[derive(Clone)]
 pub struct Background {
@@ -113,9 +214,9 @@ pub struct Background {
 }
 
 impl Background {
-    pub fn color(color: color::Colors) -> Background {
+    pub fn color(color: impl Into<color::Colors>) -> Background {
         Background {
-            color,
+            color: color.into(),
         }
     }
 }
@@ -125,21 +226,37 @@ impl Style for Background {
         return "BACKGROUND";
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property("background", &self.color.to_string());
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![("background", self.color.to_string())]
+    }
+}
+
+impl FromStr for Background {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Background, view::Error> {
+        let (_, color) = parse_property(s, &["background"])?;
+        Ok(Background { color: color.parse()? })
     }
 }
 
 #[derive(Clone)]
 pub struct BorderRadius {
-    size: size::Size,
+    declarations: Vec<(&'static str, String)>,
 }
 
 impl BorderRadius {
     pub fn new(size: size::Size) -> BorderRadius {
         BorderRadius {
-            size
+            declarations: vec![("border-radius", size.to_string())]
+        }
+    }
+
+    pub fn corners(corners: size::Corners) -> BorderRadius {
+        let names = ["border-top-left-radius", "border-top-right-radius",
+            "border-bottom-right-radius", "border-bottom-left-radius"];
+        BorderRadius {
+            declarations: each(names, corners.corners())
         }
     }
 }
@@ -149,9 +266,18 @@ impl Style for BorderRadius {
         return "BORDER_RADIUS";
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property("border-radius", &self.size.to_string());
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        self.declarations.clone()
+    }
+}
+
+impl FromStr for BorderRadius {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<BorderRadius, view::Error> {
+        let properties = ["border-radius", "border-top-left-radius", "border-top-right-radius",
+            "border-bottom-right-radius", "border-bottom-left-radius"];
+        Ok(BorderRadius { declarations: parse_values::<size::Size>(s, &properties)? })
     }
 }
 
@@ -161,15 +287,27 @@ pub struct TextDecoration {
 }
 
 impl TextDecoration {
-    pub fn new(val: &'static str) -> TextDecoration {
+    pub fn none() -> TextDecoration {
         TextDecoration {
-            val,
+            val: "none",
         }
     }
 
-    pub fn none() -> TextDecoration {
+    pub fn underline() -> TextDecoration {
         TextDecoration {
-            val: "none",
+            val: "underline",
+        }
+    }
+
+    pub fn overline() -> TextDecoration {
+        TextDecoration {
+            val: "overline",
+        }
+    }
+
+    pub fn line_through() -> TextDecoration {
+        TextDecoration {
+            val: "line-through",
         }
     }
 }
@@ -179,9 +317,17 @@ impl Style for TextDecoration {
         return "TEXT_DECORATION";
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property("text-decoration", &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![("text-decoration", self.val.to_string())]
+    }
+}
+
+impl FromStr for TextDecoration {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<TextDecoration, view::Error> {
+        let (name, val) = parse_property(s, &["text-decoration"])?;
+        Ok(TextDecoration { val: keyword(name, &val, &TEXT_DECORATIONS)? })
     }
 }
 
@@ -240,9 +386,17 @@ impl Style for TextTransform {
         return "TEXT_TRANSFORM";
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property("text-transform", &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![("text-transform", self.val.to_string())]
+    }
+}
+
+impl FromStr for TextTransform {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<TextTransform, view::Error> {
+        let (name, val) = parse_property(s, &["text-transform"])?;
+        Ok(TextTransform { val: keyword(name, &val, &TEXT_TRANSFORMS)? })
     }
 }
 
@@ -324,23 +478,36 @@ impl Style for BorderStyle {
         return "BORDER_STYLE";
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for BorderStyle {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<BorderStyle, view::Error> {
+        let (name, val) = parse_property(s, &["border-style"])?;
+        Ok(BorderStyle { name, val: keyword(name, &val, &LINE_STYLES)? })
     }
 }
 
 #[derive(Clone)]
 pub struct BorderWidth {
-    name: &'static str,
-    size: size::Size,
+    declarations: Vec<(&'static str, String)>,
 }
 
 impl BorderWidth {
     pub fn new(size: size::Size) -> BorderWidth {
         BorderWidth {
-            name: "border-width",
-            size,
+            declarations: vec![("border-width", size.to_string())],
+        }
+    }
+
+    pub fn edges(edge: size::Edge) -> BorderWidth {
+        let names = ["border-top-width", "border-right-width", "border-bottom-width", "border-left-width"];
+        BorderWidth {
+            declarations: each(names, edge.sides()),
         }
     }
 }
@@ -350,23 +517,37 @@ impl Style for BorderWidth {
         return "BORDER_WIDTH";
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property("border-width", &self.size.to_string());
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        self.declarations.clone()
+    }
+}
+
+impl FromStr for BorderWidth {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<BorderWidth, view::Error> {
+        let properties = ["border-width", "border-top-width", "border-right-width",
+            "border-bottom-width", "border-left-width"];
+        Ok(BorderWidth { declarations: parse_values::<size::Size>(s, &properties)? })
     }
 }
 
 #[derive(Clone)]
 pub struct BorderColor {
-    name: &'static str,
-    color: color::Colors,
+    declarations: Vec<(&'static str, String)>,
 }
 
 impl BorderColor {
-    pub fn new(color: color::Colors) -> BorderColor {
+    pub fn new(color: impl Into<color::Colors>) -> BorderColor {
+        BorderColor {
+            declarations: vec![("border-color", color.into().to_string())],
+        }
+    }
+
+    pub fn edges(edge: size::Edge<color::Colors>) -> BorderColor {
+        let names = ["border-top-color", "border-right-color", "border-bottom-color", "border-left-color"];
         BorderColor {
-            name: "border-color",
-            color,
+            declarations: each(names, edge.sides()),
         }
     }
 }
@@ -376,9 +557,79 @@ impl Style for BorderColor {
         return "BORDER_COLOR";
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property("border-color", &self.color.to_string());
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        self.declarations.clone()
+    }
+}
+
+impl FromStr for BorderColor {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<BorderColor, view::Error> {
+        let properties = ["border-color", "border-top-color", "border-right-color",
+            "border-bottom-color", "border-left-color"];
+        Ok(BorderColor { declarations: parse_values::<color::Colors>(s, &properties)? })
+    }
+}
+
+// Border sets the style, width and color of every side at once
+#[derive(Clone)]
+pub struct Border {
+    declarations: Vec<(&'static str, String)>,
+}
+
+impl Border {
+    pub fn none() -> Border {
+        Border {
+            declarations: vec![("border-style", "none".to_string())],
+        }
+    }
+
+    pub fn solid(width: size::Size, color: impl Into<color::Colors>) -> Border {
+        Border::line("solid", width, color.into())
+    }
+
+    pub fn dashed(width: size::Size, color: impl Into<color::Colors>) -> Border {
+        Border::line("dashed", width, color.into())
+    }
+
+    pub fn dotted(width: size::Size, color: impl Into<color::Colors>) -> Border {
+        Border::line("dotted", width, color.into())
+    }
+
+    fn line(style: &'static str, width: size::Size, color: color::Colors) -> Border {
+        Border {
+            declarations: vec![
+                ("border-style", style.to_string()),
+                ("border-width", width.to_string()),
+                ("border-color", color.to_string()),
+            ],
+        }
+    }
+}
+
+impl Style for Border {
+    fn name(&self) -> &'static str {
+        return "BORDER";
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        self.declarations.clone()
+    }
+}
+
+impl FromStr for Border {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Border, view::Error> {
+        let declarations = parse_properties(s, &["border-style", "border-width", "border-color"])?.into_iter()
+            .map(|(name, value)| match name {
+                "border-style" => Ok((name, keyword(name, &value, &LINE_STYLES)?.to_string())),
+                "border-width" => Ok((name, value.parse::<size::Size>()?.to_string())),
+                _ => Ok((name, value.parse::<color::Colors>()?.to_string())),
+            })
+            .collect::<Result<_, view::Error>>()?;
+        Ok(Border { declarations })
     }
 }
 
@@ -449,37 +700,116 @@ impl Style for Outline {
         return "OUTLINE";
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property("outline", &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![("outline", self.val.to_string())]
+    }
+}
+
+impl FromStr for Outline {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Outline, view::Error> {
+        let (name, val) = parse_property(s, &["outline"])?;
+        Ok(Outline { val: keyword(name, &val, &LINE_STYLES)? })
+    }
+}
+
+// BoxShadow draws shadows around an element, the first one on top
+#[derive(Clone)]
+pub struct BoxShadow {
+    val: String,
+}
+
+impl BoxShadow {
+    pub fn new(shadow: shadow::Shadow) -> BoxShadow {
+        BoxShadow::layers(vec![shadow])
+    }
+
+    pub fn layers(shadows: Vec<shadow::Shadow>) -> BoxShadow {
+        let shadows: Vec<String> = shadows.iter().map(|shadow| shadow.to_string()).collect();
+        BoxShadow {
+            val: shadows.join(", "),
+        }
+    }
+
+    pub fn none() -> BoxShadow {
+        BoxShadow {
+            val: "none".to_string(),
+        }
+    }
+}
+
+impl Style for BoxShadow {
+    fn name(&self) -> &'static str {
+        return "BOX_SHADOW";
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![("box-shadow", self.val.clone())]
+    }
+}
+
+impl FromStr for BoxShadow {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<BoxShadow, view::Error> {
+        let (_, val) = parse_property(s, &["box-shadow"])?;
+        if val.eq_ignore_ascii_case("none") {
+            return Ok(BoxShadow::none());
+        }
+        let shadows = shadow::split(&val, ',').into_iter()
+            .map(|shadow| shadow.parse())
+            .collect::<Result<Vec<shadow::Shadow>, view::Error>>()?;
+        match shadows.is_empty() {
+            true => Err(invalid("box-shadow", &val)),
+            false => Ok(BoxShadow::layers(shadows)),
+        }
     }
 }
 
 #[derive(Clone)]
 pub struct Margin {
     name: &'static str,
-    val: size::Size,
+    declarations: Vec<(&'static str, String)>,
 }
 
 impl Margin {
-    pub fn new(size: size::Size) -> Padding {
-        Padding { name: "margin", val: size }
+    fn side(name: &'static str, size: size::Size) -> Margin {
+        Margin { name, declarations: vec![(name, size.to_string())] }
     }
 
-    pub fn block(size: size::Size) -> Padding {
-        Padding { name: "margin-block", val: size }
+    pub fn new(size: size::Size) -> Margin {
+        Margin::side("margin", size)
     }
 
-    pub fn inline(size: size::Size) -> Padding {
-        Padding { name: "margin-inline", val: size }
+    pub fn block(size: size::Size) -> Margin {
+        Margin::side("margin-block", size)
     }
 
-    pub fn right(size: size::Size) -> Padding {
-        Padding { name: "margin-left", val: size }
+    pub fn inline(size: size::Size) -> Margin {
+        Margin::side("margin-inline", size)
     }
 
-    pub fn left(size: size::Size) -> Padding {
-        Padding { name: "margin-left", val: size }
+    pub fn top(size: size::Size) -> Margin {
+        Margin::side("margin-top", size)
+    }
+
+    pub fn right(size: size::Size) -> Margin {
+        Margin::side("margin-right", size)
+    }
+
+    pub fn bottom(size: size::Size) -> Margin {
+        Margin::side("margin-bottom", size)
+    }
+
+    pub fn left(size: size::Size) -> Margin {
+        Margin::side("margin-left", size)
+    }
+
+    // Replaces the margin of every side, sides left out of edge get none
+    pub fn edges(edge: size::Edge) -> Margin {
+        let names = ["margin-top", "margin-right", "margin-bottom", "margin-left"];
+        Margin { name: "margin", declarations: each(names, edge.sides()) }
     }
 }
 
@@ -488,33 +818,70 @@ impl Style for Margin {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val.to_string());
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        self.declarations.clone()
+    }
+}
+
+// One or more margin properties, named after the property when there is only one
+impl FromStr for Margin {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Margin, view::Error> {
+        let properties = ["margin", "margin-block", "margin-inline", "margin-top", "margin-right",
+            "margin-bottom", "margin-left"];
+        let declarations = parse_values::<size::Size>(s, &properties)?;
+        let name = match declarations.as_slice() {
+            [(name, _)] => name,
+            _ => "margin",
+        };
+        Ok(Margin { name, declarations })
     }
 }
 
 #[derive(Clone)]
 pub struct Padding {
     name: &'static str,
-    val: size::Size,
+    declarations: Vec<(&'static str, String)>,
 }
 
 impl Padding {
+    fn side(name: &'static str, size: size::Size) -> Padding {
+        Padding { name, declarations: vec![(name, size.to_string())] }
+    }
+
     pub fn new(size: size::Size) -> Padding {
-        Padding { name: "padding", val: size }
+        Padding::side("padding", size)
     }
 
     pub fn block(size: size::Size) -> Padding {
-        Padding { name: "padding-block", val: size }
+        Padding::side("padding-block", size)
     }
 
     pub fn inline(size: size::Size) -> Padding {
-        Padding { name: "padding-inline", val: size }
+        Padding::side("padding-inline", size)
+    }
+
+    pub fn top(size: size::Size) -> Padding {
+        Padding::side("padding-top", size)
+    }
+
+    pub fn right(size: size::Size) -> Padding {
+        Padding::side("padding-right", size)
+    }
+
+    pub fn bottom(size: size::Size) -> Padding {
+        Padding::side("padding-bottom", size)
     }
 
     pub fn left(size: size::Size) -> Padding {
-        Padding { name: "padding-left", val: size }
+        Padding::side("padding-left", size)
+    }
+
+    // Replaces the padding of every side, sides left out of edge get none
+    pub fn edges(edge: size::Edge) -> Padding {
+        let names = ["padding-top", "padding-right", "padding-bottom", "padding-left"];
+        Padding { name: "padding", declarations: each(names, edge.sides()) }
     }
 }
 
@@ -523,37 +890,53 @@ impl Style for Padding {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val.to_string());
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        self.declarations.clone()
+    }
+}
+
+// One or more padding properties, named after the property when there is only one
+impl FromStr for Padding {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Padding, view::Error> {
+        let properties = ["padding", "padding-block", "padding-inline", "padding-top", "padding-right",
+            "padding-bottom", "padding-left"];
+        let declarations = parse_values::<size::Size>(s, &properties)?;
+        let name = match declarations.as_slice() {
+            [(name, _)] => name,
+            _ => "padding",
+        };
+        Ok(Padding { name, declarations })
     }
 }
 
 #[derive(Clone)]
 pub struct FontWeight {
     name: &'static str,
-    val: &'static str,
+    val: String,
 }
 
 impl FontWeight {
-    pub fn new(size: &'static str) -> FontWeight {
-        FontWeight { name: "font-weight", val: size }
+    // A numeric weight from 1 to 1000, e.g. 400 for normal and 700 for bold
+    pub fn new(weight: u16) -> FontWeight {
+        FontWeight { name: "font-weight", val: weight.clamp(1, 1000).to_string() }
     }
 
     pub fn normal() -> FontWeight {
-        FontWeight { name: "font-weight", val: "normal" }
+        FontWeight { name: "font-weight", val: "normal".to_string() }
     }
 
     pub fn bold() -> FontWeight {
-        FontWeight { name: "font-weight", val: "bold" }
+        FontWeight { name: "font-weight", val: "bold".to_string() }
     }
 
     pub fn bolder() -> FontWeight {
-        FontWeight { name: "font-weight", val: "bolder" }
+        FontWeight { name: "font-weight", val: "bolder".to_string() }
     }
 
     pub fn lighter() -> FontWeight {
-        FontWeight { name: "font-weight", val: "lighter" }
+        FontWeight { name: "font-weight", val: "lighter".to_string() }
     }
 }
 
@@ -562,9 +945,22 @@ impl Style for FontWeight {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for FontWeight {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<FontWeight, view::Error> {
+        let (name, val) = parse_property(s, &["font-weight"])?;
+        let val = match val.parse::<u16>() {
+            Ok(weight) if (1..=1000).contains(&weight) => weight.to_string(),
+            Ok(_) => return Err(invalid(name, &val)),
+            Err(_) => keyword(name, &val, &["normal", "bold", "bolder", "lighter"])?.to_string(),
+        };
+        Ok(FontWeight { name, val })
     }
 }
 
@@ -576,7 +972,7 @@ pub struct FontSize {
 
 impl FontSize {
     pub fn new(size: size::Size) -> FontSize {
-        FontSize { name: "font-weight", val: size.to_string() }
+        FontSize { name: "font-size", val: size.to_string() }
     }
 }
 
@@ -585,9 +981,17 @@ impl Style for FontSize {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for FontSize {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<FontSize, view::Error> {
+        let (name, val) = parse_property(s, &["font-size"])?;
+        Ok(FontSize { name, val: val.parse::<size::Size>()?.to_string() })
     }
 }
 
@@ -608,9 +1012,17 @@ impl Style for FontFamily {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for FontFamily {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<FontFamily, view::Error> {
+        let (name, val) = parse_property(s, &["font-family"])?;
+        Ok(FontFamily { name, val: val.parse::<font::Font>()?.to_string() })
     }
 }
 
@@ -635,9 +1047,21 @@ impl Style for LetterSpacing {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for LetterSpacing {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<LetterSpacing, view::Error> {
+        let (name, val) = parse_property(s, &["letter-spacing"])?;
+        let val = match val.eq_ignore_ascii_case("normal") {
+            true => "normal".to_string(),
+            false => val.parse::<size::Size>()?.to_string(),
+        };
+        Ok(LetterSpacing { name, val })
     }
 }
 
@@ -662,9 +1086,82 @@ impl Style for Cursor {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Cursor, view::Error> {
+        let (name, val) = parse_property(s, &["cursor"])?;
+        Ok(Cursor { name, val: keyword(name, &val, &CURSORS)? })
+    }
+}
+
+// DisplayMode is how an element is laid out, and how it lays out its children
+#[derive(Clone)]
+pub struct DisplayMode {
+    name: &'static str,
+    val: &'static str,
+}
+
+impl DisplayMode {
+    // On a line of its own, as wide as its parent
+    pub fn block() -> DisplayMode {
+        DisplayMode { name: "display", val: "block" }
+    }
+
+    // In the flow of the text around it
+    pub fn inline() -> DisplayMode {
+        DisplayMode { name: "display", val: "inline" }
+    }
+
+    pub fn inline_block() -> DisplayMode {
+        DisplayMode { name: "display", val: "inline-block" }
+    }
+
+    // Lays its children out in a row or a column, see Row and Column
+    pub fn flex() -> DisplayMode {
+        DisplayMode { name: "display", val: "flex" }
+    }
+
+    pub fn inline_flex() -> DisplayMode {
+        DisplayMode { name: "display", val: "inline-flex" }
+    }
+
+    // Lays its children out in columns and rows, see Grid
+    pub fn grid() -> DisplayMode {
+        DisplayMode { name: "display", val: "grid" }
+    }
+
+    pub fn inline_grid() -> DisplayMode {
+        DisplayMode { name: "display", val: "inline-grid" }
+    }
+
+    // Not shown and taking no space
+    pub fn none() -> DisplayMode {
+        DisplayMode { name: "display", val: "none" }
+    }
+}
+
+impl Style for DisplayMode {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for DisplayMode {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<DisplayMode, view::Error> {
+        let (name, val) = parse_property(s, &["display"])?;
+        Ok(DisplayMode { name, val: keyword(name, &val, &["block", "inline", "inline-block", "flex", "inline-flex", "grid", "inline-grid", "none"])? })
     }
 }
 
@@ -675,12 +1172,12 @@ pub(crate) struct ContainerDirection {
 }
 
 impl ContainerDirection {
-    pub fn column() -> Cursor {
-        Cursor { name: "flex-direction", val: "column" }
+    pub fn column() -> ContainerDirection {
+        ContainerDirection { name: "flex-direction", val: "column" }
     }
 
-    pub fn row() -> Cursor {
-        Cursor { name: "flex-direction", val: "row" }
+    pub fn row() -> ContainerDirection {
+        ContainerDirection { name: "flex-direction", val: "row" }
     }
 }
 
@@ -689,9 +1186,17 @@ impl Style for ContainerDirection {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for ContainerDirection {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<ContainerDirection, view::Error> {
+        let (name, val) = parse_property(s, &["flex-direction"])?;
+        Ok(ContainerDirection { name, val: keyword(name, &val, &["row", "row-reverse", "column", "column-reverse"])? })
     }
 }
 
@@ -738,9 +1243,18 @@ impl Style for Justify {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for Justify {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Justify, view::Error> {
+        let (name, val) = parse_property(s, &["justify-content"])?;
+        let keywords = ["center", "flex-start", "flex-end", "space-between", "space-around", "space-evenly"];
+        Ok(Justify { name, val: keyword(name, &val, &keywords)? })
     }
 }
 
@@ -777,8 +1291,965 @@ impl Style for Align {
         return self.name;
     }
 
-    fn build(&self, element: &web_sys::HtmlElement) -> Result<(), view::Error> {
-        element.style().set_property(self.name, &self.val);
-        return Ok(());
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for Align {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Align, view::Error> {
+        let (name, val) = parse_property(s, &["align-items"])?;
+        Ok(Align { name, val: keyword(name, &val, &["center", "flex-start", "flex-end", "stretch", "baseline"])? })
+    }
+}
+
+// FlexWrap is whether a flex container moves children that do not fit onto new lines
+#[derive(Clone)]
+pub struct FlexWrap {
+    name: &'static str,
+    val: &'static str,
+}
+
+impl FlexWrap {
+    // Default value. Children stay on one line, shrinking or overflowing
+    pub fn nowrap() -> FlexWrap {
+        FlexWrap { name: "flex-wrap", val: "nowrap" }
+    }
+
+    pub fn wrap() -> FlexWrap {
+        FlexWrap { name: "flex-wrap", val: "wrap" }
+    }
+
+    // New lines go before the first one
+    pub fn wrap_reverse() -> FlexWrap {
+        FlexWrap { name: "flex-wrap", val: "wrap-reverse" }
+    }
+}
+
+impl Style for FlexWrap {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for FlexWrap {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<FlexWrap, view::Error> {
+        let (name, val) = parse_property(s, &["flex-wrap"])?;
+        Ok(FlexWrap { name, val: keyword(name, &val, &["nowrap", "wrap", "wrap-reverse"])? })
+    }
+}
+
+// Gap is the space between the children of a flex or grid container. The gaps between
+// rows and between columns are one style, so setting one after the other keeps both.
+#[derive(Clone)]
+pub struct Gap {
+    row: Option<size::Size>,
+    column: Option<size::Size>,
+}
+
+impl Gap {
+    pub fn new(size: size::Size) -> Gap {
+        Gap { row: Some(size.clone()), column: Some(size) }
+    }
+
+    // Between rows only
+    pub fn row(size: size::Size) -> Gap {
+        Gap { row: Some(size), column: None }
+    }
+
+    // Between columns only
+    pub fn column(size: size::Size) -> Gap {
+        Gap { row: None, column: Some(size) }
+    }
+
+    // Both gaps, those set in other replacing these
+    pub fn and(self, other: Gap) -> Gap {
+        Gap { row: other.row.or(self.row), column: other.column.or(self.column) }
+    }
+}
+
+impl Style for Gap {
+    fn name(&self) -> &'static str {
+        return "GAP";
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        match (&self.row, &self.column) {
+            (Some(row), Some(column)) if row == column => vec![("gap", row.to_string())],
+            (row, column) => [("row-gap", row), ("column-gap", column)].into_iter()
+                .filter_map(|(name, size)| size.as_ref().map(|size| (name, size.to_string())))
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for Gap {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Gap, view::Error> {
+        let mut gap = Gap { row: None, column: None };
+        for (name, val) in parse_properties(s, &["gap", "row-gap", "column-gap"])? {
+            let size: size::Size = val.parse()?;
+            gap = gap.and(match name {
+                "row-gap" => Gap::row(size),
+                "column-gap" => Gap::column(size),
+                _ => Gap::new(size),
+            });
+        }
+        match gap.row.is_some() || gap.column.is_some() {
+            true => Ok(gap),
+            false => Err(view::Error::InvalidDeclaration(s.trim().to_string())),
+        }
+    }
+}
+
+// FlexGrow is how much of the free space of a flex container a child takes,
+// relative to its siblings. 0, the default, takes none.
+#[derive(Clone)]
+pub struct FlexGrow {
+    name: &'static str,
+    val: String,
+}
+
+impl FlexGrow {
+    pub fn new(grow: f32) -> FlexGrow {
+        FlexGrow { name: "flex-grow", val: flex_factor(grow).to_string() }
+    }
+}
+
+impl Style for FlexGrow {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.clone())]
+    }
+}
+
+impl FromStr for FlexGrow {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<FlexGrow, view::Error> {
+        let (name, val) = parse_property(s, &["flex-grow"])?;
+        Ok(FlexGrow::new(factor(name, &val)?))
+    }
+}
+
+// FlexShrink is how much a child of a flex container shrinks, relative to its siblings,
+// when they do not fit. 1 is the default, 0 keeps its size.
+#[derive(Clone)]
+pub struct FlexShrink {
+    name: &'static str,
+    val: String,
+}
+
+impl FlexShrink {
+    pub fn new(shrink: f32) -> FlexShrink {
+        FlexShrink { name: "flex-shrink", val: flex_factor(shrink).to_string() }
+    }
+}
+
+impl Style for FlexShrink {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.clone())]
+    }
+}
+
+impl FromStr for FlexShrink {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<FlexShrink, view::Error> {
+        let (name, val) = parse_property(s, &["flex-shrink"])?;
+        Ok(FlexShrink::new(factor(name, &val)?))
+    }
+}
+
+// FlexBasis is the size of a child of a flex container before it grows or shrinks
+#[derive(Clone)]
+pub struct FlexBasis {
+    name: &'static str,
+    val: size::Size,
+}
+
+impl FlexBasis {
+    pub fn new(size: size::Size) -> FlexBasis {
+        FlexBasis { name: "flex-basis", val: size }
+    }
+
+    // Default value. The child's width or height, or the size of its content
+    pub fn auto() -> FlexBasis {
+        FlexBasis { name: "flex-basis", val: size::Size::Auto }
+    }
+}
+
+impl Style for FlexBasis {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for FlexBasis {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<FlexBasis, view::Error> {
+        let (name, val) = parse_property(s, &["flex-basis"])?;
+        Ok(FlexBasis { name, val: val.parse()? })
+    }
+}
+
+// AlignSelf aligns a child of a flex container across its direction, overriding Align
+#[derive(Clone)]
+pub struct AlignSelf {
+    name: &'static str,
+    val: &'static str,
+}
+
+impl AlignSelf {
+    // Default value. Aligned as the container's Align says
+    pub fn auto() -> AlignSelf {
+        AlignSelf { name: "align-self", val: "auto" }
+    }
+
+    pub fn center() -> AlignSelf {
+        AlignSelf { name: "align-self", val: "center" }
+    }
+
+    pub fn start() -> AlignSelf {
+        AlignSelf { name: "align-self", val: "flex-start" }
+    }
+
+    pub fn end() -> AlignSelf {
+        AlignSelf { name: "align-self", val: "flex-end" }
+    }
+
+    pub fn stretch() -> AlignSelf {
+        AlignSelf { name: "align-self", val: "stretch" }
+    }
+
+    pub fn baseline() -> AlignSelf {
+        AlignSelf { name: "align-self", val: "baseline" }
+    }
+}
+
+impl Style for AlignSelf {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for AlignSelf {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<AlignSelf, view::Error> {
+        let (name, val) = parse_property(s, &["align-self"])?;
+        Ok(AlignSelf { name, val: keyword(name, &val, &["auto", "center", "flex-start", "flex-end", "stretch", "baseline"])? })
+    }
+}
+
+// Ratio keeps the width and height of an element in proportion,
+// the one that is not set follows the other
+#[derive(Clone)]
+pub struct Ratio {
+    name: &'static str,
+    val: String,
+}
+
+impl Ratio {
+    // e.g. new(16.0, 9.0) for a video. Sides that are not positive numbers give auto.
+    pub fn new(width: f32, height: f32) -> Ratio {
+        if ![width, height].iter().all(|side| side.is_finite() && *side > 0.0) {
+            return Ratio::auto();
+        }
+        Ratio { name: "aspect-ratio", val: format!("{} / {}", width, height) }
+    }
+
+    pub fn auto() -> Ratio {
+        Ratio { name: "aspect-ratio", val: "auto".to_string() }
+    }
+}
+
+impl Style for Ratio {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.clone())]
+    }
+}
+
+// Parses "auto", a ratio like "16 / 9" or a single number, which is that number to 1
+impl FromStr for Ratio {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Ratio, view::Error> {
+        let (name, val) = parse_property(s, &["aspect-ratio"])?;
+        if val.eq_ignore_ascii_case("auto") {
+            return Ok(Ratio::auto());
+        }
+        let (width, height) = val.split_once('/').unwrap_or((&val, "1"));
+        let term = |term: &str| term.trim().parse::<f32>().ok()
+            .filter(|term| term.is_finite() && *term > 0.0)
+            .ok_or_else(|| invalid(name, &val));
+        Ok(Ratio::new(term(width)?, term(height)?))
+    }
+}
+
+// GridTemplate sizes the columns or rows of a grid container
+#[derive(Clone)]
+pub struct GridTemplate {
+    name: &'static str,
+    val: grid::Template,
+}
+
+impl GridTemplate {
+    pub fn columns(template: grid::Template) -> GridTemplate {
+        GridTemplate { name: "grid-template-columns", val: template }
+    }
+
+    pub fn rows(template: grid::Template) -> GridTemplate {
+        GridTemplate { name: "grid-template-rows", val: template }
+    }
+}
+
+impl Style for GridTemplate {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for GridTemplate {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<GridTemplate, view::Error> {
+        let (name, val) = parse_property(s, &["grid-template-columns", "grid-template-rows"])?;
+        Ok(GridTemplate { name, val: val.parse()? })
+    }
+}
+
+// GridTemplateAreas names the cells of a grid container, row by row. Children are placed
+// in an area with GridArea. Cells of one name must form a rectangle, "." is no area.
+#[derive(Clone)]
+pub struct GridTemplateAreas {
+    name: &'static str,
+    val: String,
+}
+
+impl GridTemplateAreas {
+    // e.g. new(vec![vec!["header", "header"], vec!["menu", "main"]]). Rows need as many cells
+    // each and cell names made of letters, digits, "-" and "_", else there are no areas.
+    pub fn new(rows: Vec<Vec<&'static str>>) -> GridTemplateAreas {
+        let columns = rows.first().map_or(0, Vec::len);
+        let valid = columns > 0 && rows.iter()
+            .all(|row| row.len() == columns && row.iter().all(|cell| grid::is_area_name(cell)));
+        if !valid {
+            return GridTemplateAreas::none();
+        }
+        let rows: Vec<String> = rows.iter().map(|row| format!("\"{}\"", row.join(" "))).collect();
+        GridTemplateAreas { name: "grid-template-areas", val: rows.join(" ") }
+    }
+
+    pub fn none() -> GridTemplateAreas {
+        GridTemplateAreas { name: "grid-template-areas", val: "none".to_string() }
+    }
+}
+
+impl Style for GridTemplateAreas {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.clone())]
+    }
+}
+
+// Parses quoted rows of names, every row with as many cells, or "none"
+impl FromStr for GridTemplateAreas {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<GridTemplateAreas, view::Error> {
+        let (name, val) = parse_property(s, &["grid-template-areas"])?;
+        if val.eq_ignore_ascii_case("none") {
+            return Ok(GridTemplateAreas::none());
+        }
+        let quoted: Vec<&str> = val.split('"').collect();
+        // Text outside the quotes, at even indices, may only be whitespace
+        if quoted.len().is_multiple_of(2) || quoted.iter().step_by(2).any(|gap| !gap.trim().is_empty()) {
+            return Err(invalid(name, &val));
+        }
+        let rows: Vec<Vec<&'static str>> = quoted.iter().skip(1).step_by(2)
+            .map(|row| row.split_whitespace().collect::<Vec<&str>>())
+            .map(|cells| match cells.iter().all(|cell| grid::is_area_name(cell)) {
+                true => Ok(cells.into_iter().map(intern).collect()),
+                false => Err(invalid(name, &val)),
+            })
+            .collect::<Result<_, view::Error>>()?;
+
+        let columns = rows.first().map_or(0, Vec::len);
+        match columns > 0 && rows.iter().all(|row| row.len() == columns) {
+            true => Ok(GridTemplateAreas::new(rows)),
+            false => Err(invalid(name, &val)),
+        }
+    }
+}
+
+// GridArea places a child of a grid container in an area named by GridTemplateAreas
+#[derive(Clone)]
+pub struct GridArea {
+    name: &'static str,
+    val: &'static str,
+}
+
+impl GridArea {
+    pub fn new(area: &'static str) -> GridArea {
+        GridArea { name: "grid-area", val: area }
+    }
+}
+
+impl Style for GridArea {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for GridArea {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<GridArea, view::Error> {
+        let (name, val) = parse_property(s, &["grid-area"])?;
+        match val != "." && grid::is_area_name(&val) {
+            true => Ok(GridArea { name, val: intern(&val) }),
+            false => Err(invalid(name, &val)),
+        }
+    }
+}
+
+// GridColumn places a child of a grid container between two column lines,
+// e.g. GridColumn::span(2) for a child two columns wide
+#[derive(Clone)]
+pub struct GridColumn {
+    name: &'static str,
+    val: String,
+}
+
+impl GridColumn {
+    // Line::At(0) and Line::Span(0) do not exist and are taken as Line::Auto
+    pub fn new(start: grid::Line, end: grid::Line) -> GridColumn {
+        GridColumn { name: "grid-column", val: format!("{} / {}", start.or_auto(), end.or_auto()) }
+    }
+
+    pub fn span(columns: u16) -> GridColumn {
+        GridColumn::new(grid::Line::Auto, grid::Line::Span(columns.max(1)))
+    }
+}
+
+impl Style for GridColumn {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.clone())]
+    }
+}
+
+impl FromStr for GridColumn {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<GridColumn, view::Error> {
+        let (_, val) = parse_property(s, &["grid-column"])?;
+        let (start, end) = lines(&val)?;
+        Ok(GridColumn::new(start, end))
+    }
+}
+
+// GridRow places a child of a grid container between two row lines
+#[derive(Clone)]
+pub struct GridRow {
+    name: &'static str,
+    val: String,
+}
+
+impl GridRow {
+    // Line::At(0) and Line::Span(0) do not exist and are taken as Line::Auto
+    pub fn new(start: grid::Line, end: grid::Line) -> GridRow {
+        GridRow { name: "grid-row", val: format!("{} / {}", start.or_auto(), end.or_auto()) }
+    }
+
+    pub fn span(rows: u16) -> GridRow {
+        GridRow::new(grid::Line::Auto, grid::Line::Span(rows.max(1)))
+    }
+}
+
+impl Style for GridRow {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.clone())]
+    }
+}
+
+impl FromStr for GridRow {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<GridRow, view::Error> {
+        let (_, val) = parse_property(s, &["grid-row"])?;
+        let (start, end) = lines(&val)?;
+        Ok(GridRow::new(start, end))
+    }
+}
+
+// Position is how an element is placed: in the flow of its siblings, or moved by Inset
+#[derive(Clone)]
+pub struct Position {
+    name: &'static str,
+    val: &'static str,
+}
+
+impl Position {
+    // Default value, "static". In the flow, Inset and ZIndex have no effect.
+    pub fn flow() -> Position {
+        Position { name: "position", val: "static" }
+    }
+
+    // In the flow, then moved by Inset. Elements positioned absolute inside are placed against it.
+    pub fn relative() -> Position {
+        Position { name: "position", val: "relative" }
+    }
+
+    // Out of the flow, placed by Inset against the closest ancestor that is positioned
+    pub fn absolute() -> Position {
+        Position { name: "position", val: "absolute" }
+    }
+
+    // Out of the flow, placed by Inset against the viewport, so it stays put while scrolling
+    pub fn fixed() -> Position {
+        Position { name: "position", val: "fixed" }
+    }
+
+    // In the flow, until scrolling would move it past its Inset
+    pub fn sticky() -> Position {
+        Position { name: "position", val: "sticky" }
+    }
+}
+
+impl Style for Position {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.to_string())]
+    }
+}
+
+impl FromStr for Position {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Position, view::Error> {
+        let (name, val) = parse_property(s, &["position"])?;
+        Ok(Position { name, val: keyword(name, &val, &["static", "relative", "absolute", "fixed", "sticky"])? })
+    }
+}
+
+// Inset is the offset of a positioned element from the sides it is placed against.
+// All sides are one style, widgets like Positioned keep an Edge to set them one by one.
+#[derive(Clone)]
+pub struct Inset {
+    edge: size::Edge,
+}
+
+impl Inset {
+    // The same offset from every side, e.g. 0 to cover the whole of it
+    pub fn new(size: size::Size) -> Inset {
+        Inset { edge: size::Edge::all(size) }
+    }
+
+    pub fn top(size: size::Size) -> Inset {
+        Inset { edge: size::Edge::only(size::Side::Top, size) }
+    }
+
+    pub fn right(size: size::Size) -> Inset {
+        Inset { edge: size::Edge::only(size::Side::Right, size) }
+    }
+
+    pub fn bottom(size: size::Size) -> Inset {
+        Inset { edge: size::Edge::only(size::Side::Bottom, size) }
+    }
+
+    pub fn left(size: size::Size) -> Inset {
+        Inset { edge: size::Edge::only(size::Side::Left, size) }
+    }
+
+    // The offset from every side, sides left out of edge get none
+    pub fn edges(edge: size::Edge) -> Inset {
+        Inset { edge }
+    }
+}
+
+impl Style for Inset {
+    fn name(&self) -> &'static str {
+        return "INSET";
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        match self.edge.sides() {
+            [Some(top), right, bottom, left] if [right, bottom, left].iter().all(|side| *side == Some(top)) => {
+                vec![("inset", top.to_string())]
+            }
+            sides => each(["top", "right", "bottom", "left"], sides),
+        }
+    }
+}
+
+// One or more offsets, later ones replacing earlier ones of the same side
+impl FromStr for Inset {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<Inset, view::Error> {
+        let mut edge = size::Edge { top: None, right: None, bottom: None, left: None };
+        for (name, val) in parse_properties(s, &["inset", "top", "right", "bottom", "left"])? {
+            let size: size::Size = val.parse()?;
+            edge = match name {
+                "top" => edge.top(size),
+                "right" => edge.right(size),
+                "bottom" => edge.bottom(size),
+                "left" => edge.left(size),
+                _ => size::Edge::all(size),
+            };
+        }
+        match edge.sides().iter().any(Option::is_some) {
+            true => Ok(Inset { edge }),
+            false => Err(view::Error::InvalidDeclaration(s.trim().to_string())),
+        }
+    }
+}
+
+// ZIndex orders positioned elements that overlap, higher ones are drawn on top
+#[derive(Clone)]
+pub struct ZIndex {
+    name: &'static str,
+    val: String,
+}
+
+impl ZIndex {
+    pub fn new(index: i32) -> ZIndex {
+        ZIndex { name: "z-index", val: index.to_string() }
+    }
+
+    // Default value. Drawn in the order of the document.
+    pub fn auto() -> ZIndex {
+        ZIndex { name: "z-index", val: "auto".to_string() }
+    }
+}
+
+impl Style for ZIndex {
+    fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    fn declarations(&self) -> Vec<(&'static str, String)> {
+        vec![(self.name, self.val.clone())]
+    }
+}
+
+impl FromStr for ZIndex {
+    type Err = view::Error;
+
+    fn from_str(s: &str) -> Result<ZIndex, view::Error> {
+        let (name, val) = parse_property(s, &["z-index"])?;
+        if val.eq_ignore_ascii_case("auto") {
+            return Ok(ZIndex::auto());
+        }
+        val.parse::<i32>().map(ZIndex::new).map_err(|_| invalid(name, &val))
+    }
+}
+
+// Pairs each property with its value, skipping the values that are not set
+fn each<T: fmt::Display>(names: [&'static str; 4], values: [Option<&T>; 4]) -> Vec<(&'static str, String)> {
+    names.into_iter()
+        .zip(values)
+        .filter_map(|(name, value)| value.map(|v| (name, v.to_string())))
+        .collect()
+}
+
+// Writes declarations as in a style attribute, e.g. "margin-top: 4px; margin-left: 8px"
+fn write_declarations(f: &mut fmt::Formatter<'_>, declarations: &[(&'static str, String)]) -> fmt::Result {
+    let declarations: Vec<String> = declarations.iter()
+        .map(|(name, value)| format!("{}: {}", name, value))
+        .collect();
+    write!(f, "{}", declarations.join("; "))
+}
+
+// Display writes a style as its declarations, which its FromStr parses back
+macro_rules! display_declarations {
+    ($($style:ty),*) => {
+        $(
+            impl fmt::Display for $style {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write_declarations(f, &self.declarations())
+                }
+            }
+        )*
+    };
+}
+
+display_declarations!(Custom, Width, Height, Color, Background, BorderRadius, TextDecoration,
+                      TextTransform, BorderStyle, BorderWidth, BorderColor, Border, Outline,
+                      BoxShadow, Margin, Padding, FontWeight, FontSize, FontFamily, LetterSpacing,
+                      Cursor, DisplayMode, ContainerDirection, Justify, Align, FlexWrap, Gap,
+                      FlexGrow, FlexShrink, FlexBasis, AlignSelf, Ratio, GridTemplate,
+                      GridTemplateAreas, GridArea, GridColumn, GridRow, Position, Inset, ZIndex);
+
+// Splits text like "margin-top: 4px; margin-left: 8px" into properties and values.
+// Property names are lowercased, except custom ones which are case sensitive.
+fn parse_declarations(text: &str) -> Result<Vec<(String, String)>, view::Error> {
+    let invalid = |declaration: &str| view::Error::InvalidDeclaration(declaration.trim().to_string());
+    let declarations: Vec<(String, String)> = text.split(';')
+        .filter(|declaration| !declaration.trim().is_empty())
+        .map(|declaration| {
+            let (name, value) = declaration.split_once(':').ok_or_else(|| invalid(declaration))?;
+            let (name, value) = (name.trim(), value.trim());
+            if name.is_empty() || value.is_empty() || value.contains(['{', '}']) {
+                return Err(invalid(declaration));
+            }
+            let name = match name.starts_with("--") {
+                true => name.to_string(),
+                false => name.to_ascii_lowercase(),
+            };
+            Ok((name, value.to_string()))
+        })
+        .collect::<Result<_, _>>()?;
+
+    match declarations.is_empty() {
+        true => Err(invalid(text)),
+        false => Ok(declarations),
+    }
+}
+
+// The only declaration in text
+fn parse_declaration(text: &str) -> Result<(String, String), view::Error> {
+    let mut declarations = parse_declarations(text)?;
+    match declarations.len() {
+        1 => Ok(declarations.remove(0)),
+        _ => Err(view::Error::InvalidDeclaration(text.trim().to_string())),
+    }
+}
+
+// Declarations of the given properties, any other property is refused
+fn parse_properties(text: &str, properties: &[&'static str]) -> Result<Vec<(&'static str, String)>, view::Error> {
+    parse_declarations(text)?.into_iter()
+        .map(|(name, value)| match properties.iter().find(|property| **property == name) {
+            Some(property) => Ok((*property, value)),
+            None => Err(view::Error::UnexpectedProperty(name)),
+        })
+        .collect()
+}
+
+// The only declaration in text, of one of the given properties
+fn parse_property(text: &str, properties: &[&'static str]) -> Result<(&'static str, String), view::Error> {
+    let mut declarations = parse_properties(text, properties)?;
+    match declarations.len() {
+        1 => Ok(declarations.remove(0)),
+        _ => Err(view::Error::InvalidDeclaration(text.trim().to_string())),
+    }
+}
+
+// Declarations of the given properties whose values are all a T, written back the way T writes them
+fn parse_values<T>(text: &str, properties: &[&'static str]) -> Result<Vec<(&'static str, String)>, view::Error>
+    where T: FromStr<Err = view::Error> + fmt::Display {
+    parse_properties(text, properties)?.into_iter()
+        .map(|(name, value)| Ok((name, value.parse::<T>()?.to_string())))
+        .collect()
+}
+
+fn keyword(property: &str, value: &str, keywords: &[&'static str]) -> Result<&'static str, view::Error> {
+    let value = value.to_ascii_lowercase();
+    keywords.iter()
+        .find(|keyword| **keyword == value)
+        .copied()
+        .ok_or_else(|| invalid(property, &value))
+}
+
+// Start and end line of a grid-column or grid-row value, the end is auto if left out
+fn lines(value: &str) -> Result<(grid::Line, grid::Line), view::Error> {
+    match value.split_once('/') {
+        Some((start, end)) => Ok((start.parse()?, end.parse()?)),
+        None => Ok((value.parse()?, grid::Line::Auto)),
+    }
+}
+
+// A flex-grow or flex-shrink factor, a number that is not negative
+fn factor(property: &str, value: &str) -> Result<f32, view::Error> {
+    value.parse::<f32>().ok()
+        .filter(|factor| factor.is_finite() && *factor >= 0.0)
+        .ok_or_else(|| invalid(property, value))
+}
+
+// A factor a browser accepts, 0 for negative numbers, infinity and NaN
+fn flex_factor(factor: f32) -> f32 {
+    if factor.is_finite() { factor.max(0.0) } else { 0.0 }
+}
+
+fn invalid(property: &str, value: &str) -> view::Error {
+    view::Error::InvalidValue(property.to_string(), value.to_string())
+}
+
+// Gives a name read at runtime the static lifetime style names and font families have.
+// Each distinct name is leaked once, there are only so many properties and fonts.
+pub(crate) fn intern(name: &str) -> &'static str {
+    thread_local! {
+        static NAMES: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+    }
+    NAMES.with(|names| {
+        let mut names = names.borrow_mut();
+        if let Some(name) = names.get(name) {
+            return *name;
+        }
+        let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+        names.insert(name);
+        name
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+    use std::str::FromStr;
+    use crate::view::grid::{Line, Template};
+    use crate::view::size::{Corner, Corners, Edge, Side, Size};
+    use crate::view::style::*;
+    use crate::view::view;
+
+    // Parses text, and checks that what the style writes parses back to the same style
+    fn round_trip<T: FromStr<Err = view::Error> + fmt::Display>(text: &str) -> String {
+        let written = text.parse::<T>().unwrap().to_string();
+        assert_eq!(written.parse::<T>().unwrap().to_string(), written);
+        written
+    }
+
+    #[test]
+    fn styles_round_trip() {
+        assert_eq!(round_trip::<Width>("width: calc(1rem + 4px)"), "width: calc(1rem + 4px)");
+        assert_eq!(round_trip::<Width>("max-width: min(100%, 600px)"), "max-width: min(100%, 600px)");
+        assert_eq!(round_trip::<Color>("color: hsl(210, 50%, 40%)"), "color: #336699");
+        assert_eq!(round_trip::<Color>("COLOR: rgba(0, 0, 0, 0.5)"), "color: rgba(0, 0, 0, 0.5)");
+        assert_eq!(round_trip::<Background>("background: #FFF"), "background: #FFFFFF");
+        assert_eq!(round_trip::<FontFamily>("font-family: \"Inter\", sans-serif"),
+                   "font-family: \"Inter\", sans-serif");
+        assert_eq!(round_trip::<FontFamily>("font-family: Open Sans"), "font-family: \"Open Sans\"");
+        assert_eq!(round_trip::<Padding>("padding-top: 4px; padding-left: 1em"),
+                   "padding-top: 4px; padding-left: 1em");
+        assert_eq!(round_trip::<Cursor>("cursor: Pointer"), "cursor: pointer");
+        assert_eq!(round_trip::<Custom>("--accent: #fff"), "--accent: #fff");
+        assert_eq!(round_trip::<Gap>("row-gap: 4px; column-gap: 8px"), "row-gap: 4px; column-gap: 8px");
+        assert_eq!(round_trip::<Gap>("gap: 8px; row-gap: 4px"), "row-gap: 4px; column-gap: 8px");
+        assert_eq!(round_trip::<Gap>("row-gap: 1em; column-gap: 1em"), "gap: 1em");
+    }
+
+    #[test]
+    fn rejects_invalid_declarations() {
+        assert!("width: 10".parse::<Width>().is_err());
+        assert!("width 10px".parse::<Width>().is_err());
+        assert!("width: calc(auto + 1px)".parse::<Width>().is_err());
+        assert!("height: 10px".parse::<Width>().is_err());
+        assert!("colour: red".parse::<Color>().is_err());
+        assert!("color: hsl(210, 50, 40%)".parse::<Color>().is_err());
+        assert!("color: rgb(256, 0, 0)".parse::<Color>().is_err());
+        assert!("color: rgba(0, 0, 0, 2)".parse::<Color>().is_err());
+        assert!("font-family: \"Inter".parse::<FontFamily>().is_err());
+        assert!("font-family: 12px".parse::<FontFamily>().is_err());
+        assert!("padding-top: 4px; margin-left: 1em".parse::<Padding>().is_err());
+        assert!("cursor: hand".parse::<Cursor>().is_err());
+        assert!("".parse::<Gap>().is_err());
+        assert!("grid-template-areas: \"a b\" \"c\"".parse::<GridTemplateAreas>().is_err());
+        assert!("grid-column: 0 / 2".parse::<GridColumn>().is_err());
+        assert!("grid-template-rows: ".parse::<GridTemplate>().is_err());
+        assert!("display: table".parse::<DisplayMode>().is_err());
+        assert!("1x: 2".parse::<Custom>().is_err());
+    }
+
+    #[test]
+    fn each_side_writes_its_own_property() {
+        let px = Size::Pixel;
+        let edge = Edge::only(Side::Top, px(1.0)).right(px(2.0)).bottom(px(3.0)).left(px(4.0));
+        assert_eq!(Margin::edges(edge.clone()).declarations(), vec![
+            ("margin-top", "1px".to_string()), ("margin-right", "2px".to_string()),
+            ("margin-bottom", "3px".to_string()), ("margin-left", "4px".to_string())]);
+        assert_eq!(Padding::edges(edge.clone()).declarations(), vec![
+            ("padding-top", "1px".to_string()), ("padding-right", "2px".to_string()),
+            ("padding-bottom", "3px".to_string()), ("padding-left", "4px".to_string())]);
+        assert_eq!(BorderWidth::edges(edge).declarations(), vec![
+            ("border-top-width", "1px".to_string()), ("border-right-width", "2px".to_string()),
+            ("border-bottom-width", "3px".to_string()), ("border-left-width", "4px".to_string())]);
+        let corners = Corners::only(Corner::TopLeft, px(1.0)).corner(Corner::TopRight, px(2.0))
+            .corner(Corner::BottomRight, px(3.0)).corner(Corner::BottomLeft, px(4.0));
+        assert_eq!(BorderRadius::corners(corners).declarations(), vec![
+            ("border-top-left-radius", "1px".to_string()), ("border-top-right-radius", "2px".to_string()),
+            ("border-bottom-right-radius", "3px".to_string()), ("border-bottom-left-radius", "4px".to_string())]);
+
+        // Sides left out are not written at all
+        assert_eq!(Margin::edges(Edge::only(Side::Right, px(8.0))).declarations(),
+                   vec![("margin-right", "8px".to_string())]);
+        assert_eq!(Padding::edges(Edge::only(Side::Bottom, px(8.0))).declarations(),
+                   vec![("padding-bottom", "8px".to_string())]);
+        assert_eq!(BorderWidth::edges(Edge::only(Side::Left, px(1.0))).declarations(),
+                   vec![("border-left-width", "1px".to_string())]);
+        assert_eq!(BorderRadius::corners(Corners::only(Corner::BottomLeft, px(4.0))).declarations(),
+                   vec![("border-bottom-left-radius", "4px".to_string())]);
+        assert_eq!(Margin::right(px(8.0)).declarations(), vec![("margin-right", "8px".to_string())]);
+        assert_eq!(Margin::right(px(8.0)).name(), "margin-right");
+    }
+
+    #[test]
+    fn grid_constructors_only_write_valid_values() {
+        let declaration = |style: &dyn Style| style.declarations()[0].1.clone();
+        assert_eq!(declaration(&GridTemplateAreas::new(vec![vec!["a", "b"], vec!["c"]])), "none");
+        assert_eq!(declaration(&GridTemplateAreas::new(vec![vec!["a", "1b"]])), "none");
+        assert_eq!(declaration(&GridTemplateAreas::new(Vec::new())), "none");
+        assert_eq!(declaration(&GridTemplateAreas::new(vec![vec!["a", "."]])), "\"a .\"");
+        assert_eq!(declaration(&GridColumn::new(Line::At(0), Line::At(3))), "auto / 3");
+        assert_eq!(declaration(&GridRow::new(Line::At(2), Line::Span(0))), "2 / auto");
+        assert_eq!(declaration(&GridTemplate::columns(Template::tracks(Vec::new()))), "none");
+        assert_eq!(declaration(&GridTemplate::rows(Template::repeat(0, Size::Fr(1.0)))), "none");
+        assert_eq!(declaration(&Ratio::new(0.0, 1.0)), "auto");
+        assert_eq!(declaration(&Ratio::new(f32::INFINITY, 1.0)), "auto");
+        assert_eq!(declaration(&Ratio::new(4.0, f32::NAN)), "auto");
+        assert_eq!(declaration(&Ratio::new(4.0, 3.0)), "4 / 3");
+
+        assert_eq!(round_trip::<GridTemplate>("grid-template-columns: none"), "grid-template-columns: none");
+        assert_eq!(round_trip::<GridTemplateAreas>("grid-template-areas: none"), "grid-template-areas: none");
     }
 }
\ No newline at end of file
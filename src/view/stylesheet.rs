@@ -0,0 +1,234 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use crate::backend::backend::{Backend, Element};
+use crate::view::style::{Scope, Style};
+use crate::view::view::Error;
+
+// Attribute that marks the <style> element a StyleSheet is written to
+pub const MARKER: &str = "data-style-sheet";
+
+// Rule is the body of a generated class and the scope it applies in, if any
+struct Rule {
+    scope: Option<Scope>,
+    body: String,
+}
+
+impl Rule {
+    fn css(&self, class: &str) -> String {
+        match self.scope {
+            None => format!(".{} {{ {} }}", class, self.body),
+            Some(Scope::State(state)) => format!(".{}{} {{ {} }}", class, state.selector(), self.body),
+            Some(Scope::Breakpoint(breakpoint)) => {
+                format!("{} {{ .{} {{ {} }} }}", breakpoint.media_query(), class, self.body)
+            }
+        }
+    }
+}
+
+// StyleSheet holds the CSS rules generated for widgets. Styles limited to a Scope
+// always end up here. A compiled sheet also turns plain styles into shared classes:
+// widgets with identical styles get the same class, so each rule is emitted once
+// instead of being repeated inline on every element.
+pub struct StyleSheet {
+    compiled: bool,
+    // Class name to its rule, e.g. "b-1f2e3d" to "color: white; cursor: pointer"
+    rules: RefCell<BTreeMap<String, Rule>>,
+    element: RefCell<Option<Element>>,
+    flushed: Cell<usize>,
+}
+
+impl StyleSheet {
+    // A sheet for scoped styles only, plain styles stay inline
+    pub fn new() -> StyleSheet {
+        StyleSheet { compiled: false, rules: RefCell::default(), element: RefCell::new(None), flushed: Cell::new(0) }
+    }
+
+    // A sheet that takes all styles
+    pub fn compiled() -> StyleSheet {
+        StyleSheet { compiled: true, ..StyleSheet::new() }
+    }
+
+    // Writes into an existing <style> element, e.g. one rendered on the server
+    pub fn adopt(self, element: Element) -> StyleSheet {
+        *self.element.borrow_mut() = Some(element);
+        self
+    }
+
+    pub fn is_compiled(&self) -> bool {
+        self.compiled
+    }
+
+    // Returns the class for the declarations in scope, adding its rule if it is new
+    pub fn class(&self, scope: Option<Scope>, declarations: &[(&'static str, String)]) -> String {
+        // Inline styles outrank classes, so scoped rules have to be important to show over them
+        let important = if !self.compiled && scope.is_some() { " !important" } else { "" };
+
+        let mut declarations = declarations.to_vec();
+        declarations.sort();
+        let body = declarations.iter()
+            .map(|(name, value)| format!("{}: {}{}", name, value, important))
+            .collect::<Vec<String>>()
+            .join("; ");
+
+        let rule = Rule { scope, body };
+        let hashed = format!("b-{:x}", fnv1a(rule.css("").as_bytes()));
+        let mut rules = self.rules.borrow_mut();
+        let mut class = hashed.clone();
+        // A different rule with the same hash gets a numbered class instead of sharing its one
+        for n in 1.. {
+            match rules.get(&class) {
+                Some(existing) if existing.css("") != rule.css("") => class = format!("{}-{}", hashed, n),
+                Some(_) => break,
+                None => {
+                    rules.insert(class.clone(), rule);
+                    break;
+                }
+            }
+        }
+        class
+    }
+
+    pub fn css(&self) -> String {
+        let rules = self.rules.borrow();
+        // Rules of equal specificity are decided by order, so plain rules come first,
+        // then states, then breakpoints from narrow to wide
+        let mut rules: Vec<(&String, &Rule)> = rules.iter().collect();
+        rules.sort_by_key(|(_, rule)| rule.scope);
+        rules.iter()
+            .map(|(class, rule)| rule.css(class))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    // Writes the rules into the backend's head, adding the <style> element on first use
+    pub fn flush(&self, backend: &dyn Backend) -> Result<(), Error> {
+        let count = self.rules.borrow().len();
+        if self.flushed.get() == count && (count == 0 || self.element.borrow().is_some()) {
+            return Ok(());
+        }
+
+        let mut element = self.element.borrow_mut();
+        if element.is_none() {
+            let style = backend.create_element("style")?;
+            style.set_attribute(MARKER, "")?;
+            backend.head()?.append_child(&style)?;
+            *element = Some(style);
+        }
+        if let Some(e) = element.as_ref() {
+            e.set_text(&self.css());
+        }
+        self.flushed.set(count);
+        Ok(())
+    }
+}
+
+impl Default for StyleSheet {
+    fn default() -> Self {
+        StyleSheet::new()
+    }
+}
+
+// apply styles an element. Plain styles are set inline, or as a generated class when the
+// backend's sheet is compiled. Scoped styles always become generated classes.
+pub fn apply(styles: &HashMap<&'static str, Box<dyn Style>>,
+             scoped_styles: &HashMap<(Scope, &'static str), Box<dyn Style>>,
+             element: &Element, backend: &dyn Backend) -> Result<(), Error> {
+    let sheet = backend.style_sheet();
+    let mut classes = Vec::new();
+
+    if sheet.is_compiled() {
+        let declarations: Vec<(&'static str, String)> = styles.values()
+            .flat_map(|style| style.declarations())
+            .collect();
+        if !declarations.is_empty() {
+            classes.push(sheet.class(None, &declarations));
+        }
+    } else {
+        for style in styles.values() {
+            style.build(element)?;
+        }
+    }
+
+    let mut by_scope: BTreeMap<Scope, Vec<(&'static str, String)>> = BTreeMap::new();
+    for ((scope, _), style) in scoped_styles {
+        by_scope.entry(*scope).or_default().extend(style.declarations());
+    }
+    for (scope, declarations) in by_scope {
+        classes.push(sheet.class(Some(scope), &declarations));
+    }
+
+    if !classes.is_empty() {
+        // Classes the element already has, e.g. from server rendered markup, are kept
+        let mut merged: Vec<String> = element.attribute("class").unwrap_or_default()
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        for class in classes {
+            if !merged.contains(&class) {
+                merged.push(class);
+            }
+        }
+        element.set_attribute("class", &merged.join(" "))?;
+    }
+    Ok(())
+}
+
+// flush writes the backend's style sheet into its head
+pub fn flush(backend: &dyn Backend) -> Result<(), Error> {
+    backend.style_sheet().flush(backend)
+}
+
+// FNV-1a keeps class names stable between builds, so server and browser agree on them
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::rc::Rc;
+    use crate::backend::backend::Backend;
+    use crate::backend::memory::{MemoryBackend, MemoryNode};
+    use crate::view::breakpoint::Breakpoint;
+    use crate::view::style::{Cursor, Scope, Style};
+    use crate::view::stylesheet::{apply, Rule, StyleSheet};
+
+    #[test]
+    fn keeps_the_classes_of_the_element() {
+        let backend = MemoryBackend::new().with_style_sheet(Rc::new(StyleSheet::compiled()));
+        let element = backend.create_element("div").unwrap();
+        element.set_attribute("class", "card").unwrap();
+
+        let mut styles: HashMap<&'static str, Box<dyn Style>> = HashMap::new();
+        styles.insert("cursor", Box::new(Cursor::pointer()));
+        let mut scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>> = HashMap::new();
+        scoped_styles.insert((Scope::Breakpoint(Breakpoint::Md), "cursor"), Box::new(Cursor::default()));
+        apply(&styles, &scoped_styles, &element, &backend).unwrap();
+        apply(&styles, &scoped_styles, &element, &backend).unwrap();
+
+        let classes = MemoryNode::of(&element).unwrap().attribute("class").unwrap();
+        let classes: Vec<&str> = classes.split(' ').collect();
+        assert_eq!(classes.len(), 3);
+        assert_eq!(classes[0], "card");
+        assert!(classes[1..].iter().all(|class| class.starts_with("b-")));
+    }
+
+    #[test]
+    fn numbers_classes_whose_hash_collides() {
+        let sheet = StyleSheet::compiled();
+        let class = sheet.class(None, &[("cursor", "pointer".to_string())]);
+        // Stands in for another rule that happens to hash to the same class
+        sheet.rules.borrow_mut().insert(class.clone(), Rule { scope: None, body: "color: red".to_string() });
+
+        let numbered = sheet.class(None, &[("cursor", "pointer".to_string())]);
+        assert_eq!(numbered, format!("{}-1", class));
+        assert_eq!(sheet.class(None, &[("cursor", "pointer".to_string())]), numbered);
+        assert!(sheet.css().contains(&format!(".{} {{ color: red }}", class)));
+        assert!(sheet.css().contains(&format!(".{} {{ cursor: pointer }}", numbered)));
+    }
+}
\ No newline at end of file
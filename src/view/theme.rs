@@ -0,0 +1,283 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::view::color::Srgb;
+use crate::view::font::Font;
+use crate::view::reactive::Signal;
+use crate::view::size::Size;
+
+thread_local! {
+    // The theme built-in widgets take their defaults from
+    static THEME: RefCell<Rc<Theme>> = RefCell::new(Rc::new(Theme::new()));
+    static MODE: Signal<Mode> = Signal::new(Mode::Light);
+}
+
+// Mode picks the palette of the theme. It is a signal, so effects and bindings that
+// read it run again when it changes; a built window re-renders its page.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mode {
+    Light,
+    Dark,
+}
+
+impl Mode {
+    pub fn current() -> Mode {
+        MODE.with(|m| m.get())
+    }
+
+    pub fn set(self) {
+        MODE.with(|m| {
+            if m.get() != self {
+                m.set(self);
+            }
+        });
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mode::Light => "light",
+            Mode::Dark => "dark",
+        }
+    }
+}
+
+// Theme holds the design tokens built-in widgets are styled with.
+// Override the tokens of your brand and install it before building any page:
+//
+//     Theme { light: Palette { primary: Srgb::rgb(0x0A, 0x84, 0xFF), ..Palette::new() }, ..Theme::new() }
+//         .install();
+#[derive(Clone)]
+pub struct Theme {
+    pub light: Palette,
+    pub dark: Palette,
+    pub typography: Typography,
+    pub spacing: Spacing,
+    pub radius: Radius,
+}
+
+impl Theme {
+    pub const fn new() -> Theme {
+        Theme {
+            light: Palette::new(),
+            dark: Palette::dark(),
+            typography: Typography::new(),
+            spacing: Spacing::new(),
+            radius: Radius::new(),
+        }
+    }
+
+    // Palette of the current mode
+    pub fn palette(&self) -> &Palette {
+        match Mode::current() {
+            Mode::Light => &self.light,
+            Mode::Dark => &self.dark,
+        }
+    }
+
+    // A shade of color that stands out from the background of the current mode,
+    // e.g. for hovered and pressed states
+    pub fn emphasize(&self, color: &Srgb, amount: f32) -> Srgb {
+        match Mode::current() {
+            Mode::Light => color.darken(amount),
+            Mode::Dark => color.lighten(amount),
+        }
+    }
+
+    // The installed theme
+    pub fn current() -> Rc<Theme> {
+        THEME.with(|t| Rc::clone(&t.borrow()))
+    }
+
+    // Makes this the theme widgets created from now on are styled with
+    pub fn install(self) {
+        THEME.with(|t| *t.borrow_mut() = Rc::new(self));
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::new()
+    }
+}
+
+#[derive(Clone)]
+pub struct Palette {
+    // Buttons and other main actions
+    pub primary: Srgb,
+    // Text and icons shown on primary
+    pub on_primary: Srgb,
+    // Labels and other accents
+    pub secondary: Srgb,
+    pub on_secondary: Srgb,
+    pub background: Srgb,
+    // Fields and cards shown on the background
+    pub surface: Srgb,
+    pub text: Srgb,
+    pub heading: Srgb,
+    pub link: Srgb,
+    pub border: Srgb,
+    pub disabled: Srgb,
+    pub disabled_container: Srgb,
+    pub error_container: Srgb,
+}
+
+impl Palette {
+    pub const fn new() -> Palette {
+        Palette {
+            primary: Srgb::rgb(0x42, 0x56, 0xF6),
+            on_primary: Srgb::rgb(0xFF, 0xFF, 0xFF),
+            secondary: Srgb::rgb(0xBE, 0x52, 0xF2),
+            on_secondary: Srgb::rgb(0xFF, 0xFF, 0xFF),
+            background: Srgb::rgb(0xFF, 0xFF, 0xFF),
+            surface: Srgb::rgb(0xFF, 0xFF, 0xFF),
+            text: Srgb::rgb(0x1A, 0x05, 0x1D),
+            heading: Srgb::rgb(0x00, 0x00, 0x00),
+            link: Srgb::rgb(0x3F, 0x33, 0x56),
+            border: Srgb::rgb(0xEC, 0xE9, 0xF1),
+            disabled: Srgb::rgb(0xD0, 0xC9, 0xD6),
+            disabled_container: Srgb::rgb(0xEC, 0xE9, 0xF1),
+            error_container: Srgb::rgb(0xFB, 0xE4, 0xE8),
+        }
+    }
+
+    pub const fn dark() -> Palette {
+        Palette {
+            primary: Srgb::rgb(0x42, 0x56, 0xF6),
+            on_primary: Srgb::rgb(0xFF, 0xFF, 0xFF),
+            secondary: Srgb::rgb(0xBE, 0x52, 0xF2),
+            on_secondary: Srgb::rgb(0xFF, 0xFF, 0xFF),
+            background: Srgb::rgb(0x12, 0x10, 0x16),
+            surface: Srgb::rgb(0x1E, 0x1A, 0x24),
+            text: Srgb::rgb(0xEC, 0xE9, 0xF1),
+            heading: Srgb::rgb(0xFF, 0xFF, 0xFF),
+            link: Srgb::rgb(0xD0, 0xC9, 0xD6),
+            border: Srgb::rgb(0x3F, 0x33, 0x56),
+            disabled: Srgb::rgb(0x6B, 0x5F, 0x73),
+            disabled_container: Srgb::rgb(0x2A, 0x25, 0x30),
+            error_container: Srgb::rgb(0x4A, 0x1F, 0x29),
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::new()
+    }
+}
+
+#[derive(Clone)]
+pub struct Typography {
+    pub family: Font,
+    pub body: Size,
+    pub button: Size,
+    pub input: Size,
+    pub heading: Size,
+    pub button_weight: u16,
+}
+
+impl Typography {
+    pub const fn new() -> Typography {
+        Typography {
+            family: Font::SansSerif,
+            body: Size::Pixel(16.0),
+            button: Size::Pixel(14.0),
+            input: Size::Pixel(15.0),
+            heading: Size::Pixel(22.0),
+            button_weight: 500,
+        }
+    }
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        Typography::new()
+    }
+}
+
+// Spacing is the scale paddings and gaps are picked from
+#[derive(Clone)]
+pub struct Spacing {
+    pub xs: Size,
+    pub sm: Size,
+    pub md: Size,
+    pub lg: Size,
+    pub xl: Size,
+}
+
+impl Spacing {
+    pub const fn new() -> Spacing {
+        Spacing {
+            xs: Size::Pixel(8.0),
+            sm: Size::Pixel(10.0),
+            md: Size::Pixel(14.0),
+            lg: Size::Pixel(40.0),
+            xl: Size::Pixel(45.0),
+        }
+    }
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Spacing::new()
+    }
+}
+
+#[derive(Clone)]
+pub struct Radius {
+    pub sm: Size,
+    pub md: Size,
+}
+
+impl Radius {
+    pub const fn new() -> Radius {
+        Radius { sm: Size::Pixel(5.0), md: Size::Pixel(10.0) }
+    }
+}
+
+impl Default for Radius {
+    fn default() -> Self {
+        Radius::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use crate::backend::memory::{MemoryBackend, MemoryNode};
+    use crate::view::color::Srgb;
+    use crate::view::size::Size;
+    use crate::view::theme::{Palette, Radius, Theme, Typography};
+    use crate::view::view::Viewable;
+    use crate::view::widget::Button;
+
+    fn styles(button: &mut Button) -> BTreeMap<String, String> {
+        let element = button.build(&MemoryBackend::new()).unwrap();
+        MemoryNode::of(&element).unwrap().styles()
+    }
+
+    #[test]
+    fn widgets_default_to_the_built_in_theme() {
+        let button = styles(&mut Button::primary("Save"));
+        assert_eq!(button["background"], "#4256F6");
+        assert_eq!(button["border-radius"], "5px");
+        assert_eq!(button["font-size"], "14px");
+        assert_eq!(button["font-family"], "sans-serif");
+    }
+
+    #[test]
+    fn widgets_take_the_tokens_of_the_installed_theme() {
+        Theme {
+            light: Palette { primary: Srgb::rgb(0x0A, 0x84, 0xFF), ..Palette::new() },
+            typography: Typography { button: Size::Rem(1.0), ..Typography::new() },
+            radius: Radius { sm: Size::Pixel(0.0), ..Radius::new() },
+            ..Theme::new()
+        }.install();
+        let button = styles(&mut Button::primary("Save"));
+        Theme::new().install();
+
+        assert_eq!(button["background"], "#0A84FF");
+        assert_eq!(button["border-radius"], "0px");
+        assert_eq!(button["font-size"], "1rem");
+        assert_eq!(button["font-family"], "sans-serif");
+        assert_eq!(styles(&mut Button::primary("Save"))["background"], "#4256F6");
+    }
+}
@@ -1,26 +1,27 @@
-use web_sys;
-use wasm_bindgen::JsCast;
+use crate::backend::backend::{Backend, Element};
 
 pub trait Viewable {
     fn get_tag(&self) -> &'static str { return "div"; }
-    fn get_html_element(&mut self) -> Option<&web_sys::HtmlElement>;
+    fn get_html_element(&mut self) -> Option<&Element>;
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> { Vec::new() }
 
-    fn set(&mut self, f: Box<dyn Fn(&web_sys::HtmlElement)>) {
+    // Adopts an element that already shows this view, e.g. one reused by a re-render,
+    // attaching listeners and bindings to it without rendering anything
+    fn mount(&mut self, element: Element);
+
+    fn set(&mut self, f: Box<dyn Fn(&Element)>) {
         match self.get_html_element() {
             Some(e) => { f(e) }
             None => {}
         };
     }
 
-    fn render(&mut self, element: web_sys::HtmlElement, _: &web_sys::Document)
-              -> Result<web_sys::HtmlElement, Error> { Ok(element) }
+    fn render(&mut self, element: Element, _: &dyn Backend)
+              -> Result<Element, Error> { Ok(element) }
 
-    fn build(&mut self, document: &web_sys::Document) -> Result<web_sys::HtmlElement, Error> {
-        let html_element = match document.create_element(self.get_tag()) {
-            Ok(e) => e.dyn_into::<web_sys::HtmlElement>().unwrap(),
-            Err(_) => return Err(Error::ElementCreation),
-        };
-        self.render(html_element, document)
+    fn build(&mut self, backend: &dyn Backend) -> Result<Element, Error> {
+        let html_element = backend.create_element(self.get_tag())?;
+        self.render(html_element, backend)
     }
 }
 
@@ -29,6 +30,38 @@ pub trait Viewable {
 pub enum Error {
     #[error("Failed to get body")]
     NoBodyFound,
+    #[error("Failed to get head")]
+    NoHeadFound,
     #[error("Failed to create element")]
     ElementCreation,
-}
\ No newline at end of file
+    #[error("Failed to append child")]
+    AppendChild,
+    #[error("Failed to remove child")]
+    RemoveChild,
+    #[error("Element belongs to another backend")]
+    ForeignElement,
+    #[error("Failed to set attribute {0}")]
+    Attribute(String),
+    #[error("Failed to set style property {0}")]
+    Style(String),
+    #[error("Existing markup does not match the widget tree at <{0}>")]
+    HydrationMismatch(String),
+    #[error("View has not been built yet")]
+    NotMounted,
+    #[error("Re-rendered tree has a different root <{0}>")]
+    RootChanged(String),
+    #[error("Invalid color {0}")]
+    InvalidColor(String),
+    #[error("Invalid size {0}")]
+    InvalidSize(String),
+    #[error("Invalid font family {0}")]
+    InvalidFont(String),
+    #[error("Invalid CSS declaration {0}")]
+    InvalidDeclaration(String),
+    #[error("Property {0} cannot be set by this style")]
+    UnexpectedProperty(String),
+    #[error("Invalid value {1} for {0}")]
+    InvalidValue(String, String),
+    #[error("Failed to add font {0}")]
+    FontFace(String),
+}
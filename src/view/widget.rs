@@ -1,210 +1,1481 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::ops::Deref;
-use web_sys;
-use wasm_bindgen::JsCast;
+use std::rc::Rc;
+use crate::backend::backend::{Backend, Element};
+use crate::view::breakpoint::Breakpoint;
 use crate::view::color::Colors;
-use crate::view::font::Font;
-use crate::view::size::Size;
+use crate::view::event::{Event, EventKind, Listener};
+use crate::view::reactive::Binding;
+use crate::view::grid::Template;
+use crate::view::shadow::Shadow;
+use crate::view::size::{Edge, Size};
 use crate::view::style::*;
+use crate::view::stylesheet;
+use crate::view::theme::Theme;
 use crate::view::view::{Error, Viewable};
+use crate::window::router;
 
 pub trait Widget: Styleable {}
 
 pub trait Styleable: Viewable {
     fn style(&mut self, style: impl Style + 'static + Clone) -> &mut Self {
         let style2 = style.clone();
-        self.set(Box::new(move |e| {
-            style2.build(e).unwrap();
-        }));
+        self.set(Box::new(move |e| report(style2.build(e))));
         self.store_style(style);
         self
     }
 
+    // Applies the style only while the element is in state, e.g. hovered or focused.
+    // Takes effect the next time the widget is rendered.
+    fn style_on(&mut self, state: State, style: impl Style + 'static) -> &mut Self {
+        self.store_scoped_style(Scope::State(state), style);
+        self
+    }
+
+    // Applies the style on viewports at least as wide as breakpoint.
+    // Takes effect the next time the widget is rendered.
+    fn style_at(&mut self, breakpoint: Breakpoint, style: impl Style + 'static) -> &mut Self {
+        self.store_scoped_style(Scope::Breakpoint(breakpoint), style);
+        self
+    }
+
     fn store_style(&mut self, style: impl Style + 'static);
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static);
+}
+
+pub trait Listenable: Viewable {
+    fn on(&mut self, kind: EventKind, handler: impl Fn(&Event) + 'static) -> &mut Self {
+        let mut listener = Listener::new(kind, Rc::new(handler));
+        if let Some(e) = self.get_html_element() {
+            listener.attach(e);
+        }
+        self.store_listener(listener);
+        self
+    }
+
+    fn on_click(&mut self, handler: impl Fn(&Event) + 'static) -> &mut Self {
+        self.on(EventKind::Click, handler)
+    }
+
+    fn on_input(&mut self, handler: impl Fn(&Event) + 'static) -> &mut Self {
+        self.on(EventKind::Input, handler)
+    }
+
+    fn on_change(&mut self, handler: impl Fn(&Event) + 'static) -> &mut Self {
+        self.on(EventKind::Change, handler)
+    }
+
+    fn on_focus(&mut self, handler: impl Fn(&Event) + 'static) -> &mut Self {
+        self.on(EventKind::Focus, handler)
+    }
+
+    fn on_blur(&mut self, handler: impl Fn(&Event) + 'static) -> &mut Self {
+        self.on(EventKind::Blur, handler)
+    }
+
+    fn on_key_down(&mut self, handler: impl Fn(&Event) + 'static) -> &mut Self {
+        self.on(EventKind::KeyDown, handler)
+    }
+
+    fn store_listener(&mut self, listener: Listener);
+}
+
+// Bindable widgets apply a property inside an effect, so it is updated
+// whenever a signal read by the closure changes.
+pub trait Bindable: Viewable {
+    fn bind(&mut self, apply: impl Fn(&Element) + 'static) -> &mut Self {
+        let mut binding = Binding::new(Rc::new(apply));
+        if let Some(e) = self.get_html_element() {
+            binding.attach(e);
+        }
+        self.store_binding(binding);
+        self
+    }
+
+    fn bind_style<S: Style>(&mut self, style: impl Fn() -> S + 'static) -> &mut Self {
+        self.bind(move |e| report(style().build(e)))
+    }
+
+    fn store_binding(&mut self, binding: Binding);
+}
+
+// Logs a failure to update an element from a binding. It runs in an effect, after
+// some signal was set, so there is no caller to return the error to.
+fn report(result: Result<(), Error>) {
+    if let Err(e) = result {
+        gloo::console::error!(e.to_string());
+    }
+}
+
+// Sets the attribute while on is true and removes it otherwise, e.g. disabled on an input
+fn toggle_attribute(element: &Element, name: &str, value: &str, on: bool) -> Result<(), Error> {
+    if on {
+        element.set_attribute(name, value)
+    } else {
+        element.remove_attribute(name)
+    }
+}
+
+pub struct Body {
+    child: Box<dyn Viewable>,
+    styles: HashMap<&'static str, Box<dyn Style>>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
+}
+
+impl Body {
+    pub fn new(child: impl Viewable + 'static) -> Body {
+        Body {
+            child: Box::new(child),
+            styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
+            html_element: None,
+        }
+            .apply(&|mut body| {
+                let theme = Theme::current();
+                body
+                    .style(Color::new(theme.palette().text))
+                    .style(Background::color(theme.palette().background));
+                return body;
+            })
+    }
+
+    pub fn make(self) -> Box<dyn Viewable> {
+        return Box::new(self);
+    }
+
+    pub fn apply(self, f: &dyn Fn(Body) -> Body) -> Self {
+        f(self)
+    }
+}
+
+impl Styleable for Body {
+    fn store_style(&mut self, style: impl Style + 'static) {
+        self.styles.insert(style.name(), Box::new(style));
+    }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Body {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Body {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for Body {
+    fn get_html_element(&mut self) -> Option<&Element> {
+        match &self.html_element {
+            Some(e) => { Some(e) }
+            None => None
+        }
+    }
+
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> {
+        vec![&mut self.child]
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
+
+        for binding in &mut self.bindings {
+            binding.attach(&element);
+        }
+
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        self.mount(element.clone());
+        Ok(element)
+    }
+
+    fn build(&mut self, backend: &dyn Backend) -> Result<Element, Error> {
+        let element = backend.body()?;
+
+        let child = self.child.build(backend)?;
+        element.append_child(&child)?;
+
+        self.render(element, backend)
+    }
+}
+
+pub struct Column {
+    children: Vec<Box<dyn Viewable>>,
+    styles: HashMap<&'static str, Box<dyn Style>>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
+}
+
+impl Column {
+    pub fn new() -> Column {
+        Column {
+            children: Vec::default(),
+            styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
+            html_element: None,
+        }.apply(&|mut column| {
+            column
+                .style(DisplayMode::flex())
+                .style(ContainerDirection::column())
+                .style(Justify::start());
+            return column;
+        })
+    }
+
+    pub fn child(mut self, child: impl Viewable + 'static) -> Column {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    // Space between the children
+    pub fn gap(mut self, gap: Size) -> Column {
+        self.style(Gap::new(gap));
+        self
+    }
+
+    // Moves children that do not fit into new columns
+    pub fn wrap(mut self) -> Column {
+        self.style(FlexWrap::wrap());
+        self
+    }
+
+    pub fn apply(self, f: &dyn Fn(Column) -> Column) -> Self {
+        f(self)
+    }
+}
+
+impl Default for Column {
+    fn default() -> Self {
+        Column::new()
+    }
+}
+
+impl Styleable for Column {
+    fn store_style(&mut self, style: impl Style + 'static) {
+        self.styles.insert(style.name(), Box::new(style));
+    }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Column {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Column {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for Column {
+    fn get_html_element(&mut self) -> Option<&Element> {
+        match &self.html_element {
+            Some(e) => { Some(e) }
+            None => None
+        }
+    }
+
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> {
+        self.children.iter_mut().collect()
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
+
+        for binding in &mut self.bindings {
+            binding.attach(&element);
+        }
+
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        for view in &mut self.children {
+            element.append_child(&view.build(backend)?)?;
+        }
+
+        self.mount(element.clone());
+        Ok(element)
+    }
+}
+
+pub struct Row {
+    children: Vec<Box<dyn Viewable>>,
+    styles: HashMap<&'static str, Box<dyn Style>>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
+}
+
+impl Row {
+    pub fn new() -> Row {
+        Row {
+            children: Vec::default(),
+            styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
+            html_element: None,
+        }
+            .apply(&|mut row| {
+                row
+                    .style(DisplayMode::flex())
+                    .style(ContainerDirection::row())
+                    .style(Justify::start());
+                return row;
+            })
+    }
+
+    pub fn child(mut self, child: impl Viewable + 'static) -> Row {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    // Space between the children
+    pub fn gap(mut self, gap: Size) -> Row {
+        self.style(Gap::new(gap));
+        self
+    }
+
+    // Moves children that do not fit onto new rows
+    pub fn wrap(mut self) -> Row {
+        self.style(FlexWrap::wrap());
+        self
+    }
+
+    pub fn apply(self, f: &dyn Fn(Row) -> Row) -> Self {
+        f(self)
+    }
+}
+
+impl Default for Row {
+    fn default() -> Self {
+        Row::new()
+    }
+}
+
+impl Styleable for Row {
+    fn store_style(&mut self, style: impl Style + 'static) {
+        self.styles.insert(style.name(), Box::new(style));
+    }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Row {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Row {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for Row {
+    fn get_html_element(&mut self) -> Option<&Element> {
+        match &self.html_element {
+            Some(e) => { Some(e) }
+            None => None
+        }
+    }
+
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> {
+        self.children.iter_mut().collect()
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
+
+        for binding in &mut self.bindings {
+            binding.attach(&element);
+        }
+
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        for view in &mut self.children {
+            element.append_child(&view.build(backend)?)?;
+        }
+
+        self.mount(element.clone());
+        Ok(element)
+    }
+}
+
+// Grid lays its children out in columns and rows, filling the cells in order unless
+// a child is placed with GridArea, GridColumn or GridRow. For a form with aligned labels:
+//
+//     Grid::new()
+//         .columns(Template::tracks(vec![Size::Auto.into(), Size::Fr(1.0).into()]))
+//         .gap(Size::Pixel(8.0))
+//         .child(Label::new("Name"))
+//         .child(Input::new("Jane"))
+pub struct Grid {
+    children: Vec<Box<dyn Viewable>>,
+    // Gaps set so far, row_gap and column_gap add to them
+    gap: Option<Gap>,
+    styles: HashMap<&'static str, Box<dyn Style>>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
+}
+
+impl Grid {
+    pub fn new() -> Grid {
+        Grid {
+            children: Vec::default(),
+            gap: None,
+            styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
+            html_element: None,
+        }.apply(&|mut grid| {
+            grid.style(DisplayMode::grid());
+            return grid;
+        })
+    }
+
+    pub fn child(mut self, child: impl Viewable + 'static) -> Grid {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    pub fn columns(mut self, template: Template) -> Grid {
+        self.style(GridTemplate::columns(template));
+        self
+    }
+
+    // Rows beyond the template are sized to their content
+    pub fn rows(mut self, template: Template) -> Grid {
+        self.style(GridTemplate::rows(template));
+        self
+    }
+
+    // Names the cells, row by row, see GridTemplateAreas
+    pub fn areas(mut self, rows: Vec<Vec<&'static str>>) -> Grid {
+        self.style(GridTemplateAreas::new(rows));
+        self
+    }
+
+    // Space between the columns and between the rows
+    pub fn gap(self, gap: Size) -> Grid {
+        self.add_gap(Gap::new(gap))
+    }
+
+    pub fn row_gap(self, gap: Size) -> Grid {
+        self.add_gap(Gap::row(gap))
+    }
+
+    pub fn column_gap(self, gap: Size) -> Grid {
+        self.add_gap(Gap::column(gap))
+    }
+
+    fn add_gap(mut self, gap: Gap) -> Grid {
+        let gap = match self.gap.take() {
+            Some(previous) => previous.and(gap),
+            None => gap,
+        };
+        self.gap = Some(gap.clone());
+        self.style(gap);
+        self
+    }
+
+    pub fn apply(self, f: &dyn Fn(Grid) -> Grid) -> Self {
+        f(self)
+    }
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Grid::new()
+    }
+}
+
+impl Styleable for Grid {
+    fn store_style(&mut self, style: impl Style + 'static) {
+        self.styles.insert(style.name(), Box::new(style));
+    }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Grid {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Grid {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for Grid {
+    fn get_html_element(&mut self) -> Option<&Element> {
+        match &self.html_element {
+            Some(e) => { Some(e) }
+            None => None
+        }
+    }
+
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> {
+        self.children.iter_mut().collect()
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
+
+        for binding in &mut self.bindings {
+            binding.attach(&element);
+        }
+
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        for view in &mut self.children {
+            element.append_child(&view.build(backend)?)?;
+        }
+
+        self.mount(element.clone());
+        Ok(element)
+    }
+}
+
+// Container wraps a single child in a box with padding, margin, background, border,
+// shadow and size constraints, e.g. to draw a card around a form
+pub struct Container {
+    child: Box<dyn Viewable>,
+    styles: HashMap<&'static str, Box<dyn Style>>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
+}
+
+impl Container {
+    pub fn new(child: impl Viewable + 'static) -> Container {
+        Container {
+            child: Box::new(child),
+            styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
+            html_element: None,
+        }
+    }
+
+    pub fn padding(mut self, edge: Edge) -> Container {
+        self.style(Padding::edges(edge));
+        self
+    }
+
+    pub fn margin(mut self, edge: Edge) -> Container {
+        self.style(Margin::edges(edge));
+        self
+    }
+
+    pub fn background(mut self, color: impl Into<Colors>) -> Container {
+        self.style(Background::color(color));
+        self
+    }
+
+    // A solid border on every side
+    pub fn border(mut self, width: Size, color: impl Into<Colors>) -> Container {
+        self.style(Border::solid(width, color));
+        self
+    }
+
+    pub fn radius(mut self, radius: Size) -> Container {
+        self.style(BorderRadius::new(radius));
+        self
+    }
+
+    pub fn shadow(mut self, shadow: Shadow) -> Container {
+        self.style(BoxShadow::new(shadow));
+        self
+    }
+
+    pub fn width(mut self, width: Size) -> Container {
+        self.style(Width::new(width));
+        self
+    }
+
+    pub fn min_width(mut self, width: Size) -> Container {
+        self.style(Width::min(width));
+        self
+    }
+
+    pub fn max_width(mut self, width: Size) -> Container {
+        self.style(Width::max(width));
+        self
+    }
+
+    pub fn height(mut self, height: Size) -> Container {
+        self.style(Height::new(height));
+        self
+    }
+
+    pub fn min_height(mut self, height: Size) -> Container {
+        self.style(Height::min(height));
+        self
+    }
+
+    pub fn max_height(mut self, height: Size) -> Container {
+        self.style(Height::max(height));
+        self
+    }
+
+    pub fn apply(self, f: &dyn Fn(Container) -> Container) -> Self {
+        f(self)
+    }
+}
+
+impl Styleable for Container {
+    fn store_style(&mut self, style: impl Style + 'static) {
+        self.styles.insert(style.name(), Box::new(style));
+    }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Container {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Container {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for Container {
+    fn get_html_element(&mut self) -> Option<&Element> {
+        match &self.html_element {
+            Some(e) => { Some(e) }
+            None => None
+        }
+    }
+
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> {
+        vec![&mut self.child]
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
+
+        for binding in &mut self.bindings {
+            binding.attach(&element);
+        }
+
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        element.append_child(&self.child.build(backend)?)?;
+
+        self.mount(element.clone());
+        Ok(element)
+    }
+}
+
+// Spacer takes the free space of the Row or Column it is in, e.g. to push the
+// children after it to the end. Several spacers share the space by their flex.
+pub struct Spacer {
+    styles: HashMap<&'static str, Box<dyn Style>>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
+}
+
+impl Spacer {
+    pub fn new() -> Spacer {
+        Spacer::flex(1.0)
+    }
+
+    pub fn flex(flex: f32) -> Spacer {
+        Spacer {
+            styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
+            html_element: None,
+        }.apply(&|mut spacer| {
+            spacer
+                .style(FlexGrow::new(flex))
+                .style(FlexShrink::new(1.0))
+                .style(FlexBasis::new(Size::Pixel(0.0)));
+            return spacer;
+        })
+    }
+
+    pub fn apply(self, f: &dyn Fn(Spacer) -> Spacer) -> Self {
+        f(self)
+    }
+}
+
+impl Default for Spacer {
+    fn default() -> Self {
+        Spacer::new()
+    }
+}
+
+impl Styleable for Spacer {
+    fn store_style(&mut self, style: impl Style + 'static) {
+        self.styles.insert(style.name(), Box::new(style));
+    }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Spacer {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Spacer {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for Spacer {
+    fn get_html_element(&mut self) -> Option<&Element> {
+        match &self.html_element {
+            Some(e) => { Some(e) }
+            None => None
+        }
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
+
+        for binding in &mut self.bindings {
+            binding.attach(&element);
+        }
+
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        self.mount(element.clone());
+        Ok(element)
+    }
+}
+
+// Expanded fills its child into the free space of the Row or Column it is in,
+// shared with other expanded children by their flex
+pub struct Expanded {
+    child: Box<dyn Viewable>,
+    styles: HashMap<&'static str, Box<dyn Style>>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
+}
+
+impl Expanded {
+    pub fn new(child: impl Viewable + 'static) -> Expanded {
+        Expanded::flex(1.0, child)
+    }
+
+    pub fn flex(flex: f32, child: impl Viewable + 'static) -> Expanded {
+        Expanded {
+            child: Box::new(child),
+            styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
+            html_element: None,
+        }.apply(&|mut expanded| {
+            expanded
+                .style(FlexGrow::new(flex))
+                .style(FlexShrink::new(1.0))
+                .style(FlexBasis::new(Size::Pixel(0.0)))
+                // Lets content larger than the share shrink instead of overflowing,
+                // across a Row and down a Column
+                .style(Width::min(Size::Pixel(0.0)))
+                .style(Height::min(Size::Pixel(0.0)))
+                .style(DisplayMode::flex())
+                .style(ContainerDirection::column());
+            return expanded;
+        })
+    }
+
+    pub fn apply(self, f: &dyn Fn(Expanded) -> Expanded) -> Self {
+        f(self)
+    }
+}
+
+impl Styleable for Expanded {
+    fn store_style(&mut self, style: impl Style + 'static) {
+        self.styles.insert(style.name(), Box::new(style));
+    }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Expanded {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Expanded {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for Expanded {
+    fn get_html_element(&mut self) -> Option<&Element> {
+        match &self.html_element {
+            Some(e) => { Some(e) }
+            None => None
+        }
+    }
+
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> {
+        vec![&mut self.child]
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
+
+        for binding in &mut self.bindings {
+            binding.attach(&element);
+        }
+
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        element.append_child(&self.child.build(backend)?)?;
+
+        self.mount(element.clone());
+        Ok(element)
+    }
+}
+
+// Center centers its child in the space it is given
+pub struct Center {
+    child: Box<dyn Viewable>,
+    styles: HashMap<&'static str, Box<dyn Style>>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
+}
+
+impl Center {
+    pub fn new(child: impl Viewable + 'static) -> Center {
+        Center {
+            child: Box::new(child),
+            styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
+            html_element: None,
+        }.apply(&|mut center| {
+            center
+                .style(DisplayMode::flex())
+                .style(Justify::center())
+                .style(Align::center());
+            return center;
+        })
+    }
+
+    pub fn apply(self, f: &dyn Fn(Center) -> Center) -> Self {
+        f(self)
+    }
+}
+
+impl Styleable for Center {
+    fn store_style(&mut self, style: impl Style + 'static) {
+        self.styles.insert(style.name(), Box::new(style));
+    }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Center {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Center {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for Center {
+    fn get_html_element(&mut self) -> Option<&Element> {
+        match &self.html_element {
+            Some(e) => { Some(e) }
+            None => None
+        }
+    }
+
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> {
+        vec![&mut self.child]
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
+
+        for binding in &mut self.bindings {
+            binding.attach(&element);
+        }
+
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        element.append_child(&self.child.build(backend)?)?;
+
+        self.mount(element.clone());
+        Ok(element)
+    }
+}
+
+// SizedBox has a fixed width, height or both, which a Row or Column does not shrink.
+// Without a child it is a fixed space between its siblings.
+pub struct SizedBox {
+    child: Option<Box<dyn Viewable>>,
+    styles: HashMap<&'static str, Box<dyn Style>>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
+}
+
+impl SizedBox {
+    pub fn new(width: Size, height: Size) -> SizedBox {
+        SizedBox::empty().apply(&|mut sized| {
+            sized
+                .style(Width::new(width.clone()))
+                .style(Height::new(height.clone()));
+            return sized;
+        })
+    }
+
+    pub fn width(width: Size) -> SizedBox {
+        SizedBox::empty().apply(&|mut sized| {
+            sized.style(Width::new(width.clone()));
+            return sized;
+        })
+    }
+
+    pub fn height(height: Size) -> SizedBox {
+        SizedBox::empty().apply(&|mut sized| {
+            sized.style(Height::new(height.clone()));
+            return sized;
+        })
+    }
+
+    pub fn square(size: Size) -> SizedBox {
+        SizedBox::new(size.clone(), size)
+    }
+
+    fn empty() -> SizedBox {
+        SizedBox {
+            child: None,
+            styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
+            html_element: None,
+        }.apply(&|mut sized| {
+            sized.style(FlexShrink::new(0.0));
+            return sized;
+        })
+    }
+
+    pub fn child(mut self, child: impl Viewable + 'static) -> SizedBox {
+        self.child = Some(Box::new(child));
+        self
+    }
+
+    pub fn apply(self, f: &dyn Fn(SizedBox) -> SizedBox) -> Self {
+        f(self)
+    }
+}
+
+impl Styleable for SizedBox {
+    fn store_style(&mut self, style: impl Style + 'static) {
+        self.styles.insert(style.name(), Box::new(style));
+    }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for SizedBox {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for SizedBox {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for SizedBox {
+    fn get_html_element(&mut self) -> Option<&Element> {
+        match &self.html_element {
+            Some(e) => { Some(e) }
+            None => None
+        }
+    }
+
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> {
+        self.child.iter_mut().collect()
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
+
+        for binding in &mut self.bindings {
+            binding.attach(&element);
+        }
+
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        if let Some(child) = &mut self.child {
+            element.append_child(&child.build(backend)?)?;
+        }
+
+        self.mount(element.clone());
+        Ok(element)
+    }
 }
 
-pub struct Body {
+// AspectRatio is as wide as it may be and as high as the ratio of width to height
+// makes it, e.g. AspectRatio::new(16.0 / 9.0, video). The child is stretched across it.
+pub struct AspectRatio {
     child: Box<dyn Viewable>,
     styles: HashMap<&'static str, Box<dyn Style>>,
-    html_element: Option<web_sys::HtmlElement>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
 }
 
-impl Body {
-    pub fn new(child: impl Viewable + 'static) -> Body {
-        Body {
+impl AspectRatio {
+    pub fn new(ratio: f32, child: impl Viewable + 'static) -> AspectRatio {
+        AspectRatio {
             child: Box::new(child),
             styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
             html_element: None,
-        }
-    }
-
-    pub fn make(self) -> Box<dyn Viewable> {
-        return Box::new(self);
+        }.apply(&|mut aspect| {
+            aspect
+                .style(Ratio::new(ratio, 1.0))
+                .style(Width::new(Size::Percent(100.0)))
+                .style(DisplayMode::flex())
+                .style(ContainerDirection::column());
+            return aspect;
+        })
     }
 
-    pub fn apply(self, f: &dyn Fn(Body) -> Body) -> Self {
+    pub fn apply(self, f: &dyn Fn(AspectRatio) -> AspectRatio) -> Self {
         f(self)
     }
 }
 
-impl Styleable for Body {
+impl Styleable for AspectRatio {
     fn store_style(&mut self, style: impl Style + 'static) {
         self.styles.insert(style.name(), Box::new(style));
     }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
 }
 
-impl Viewable for Body {
-    fn get_html_element(&mut self) -> Option<&web_sys::HtmlElement> {
+impl Listenable for AspectRatio {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for AspectRatio {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for AspectRatio {
+    fn get_html_element(&mut self) -> Option<&Element> {
         match &self.html_element {
             Some(e) => { Some(e) }
             None => None
         }
     }
 
-    fn render(&mut self, element: web_sys::HtmlElement, _: &web_sys::Document) -> Result<web_sys::HtmlElement, Error> {
-        for (_, mut style) in &self.styles {
-            style.build(&element)?;
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> {
+        vec![&mut self.child]
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
         }
-        Ok(element)
+
+        for binding in &mut self.bindings {
+            binding.attach(&element);
+        }
+
+        self.html_element = Some(element);
     }
 
-    fn build(&mut self, document: &web_sys::Document) -> Result<web_sys::HtmlElement, Error> {
-        let mut element = document.body().ok_or(Error::NoBodyFound)? as web_sys::HtmlElement;
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
 
-        let child = self.child.build(document)?;
-        element.append_child(&child);
+        element.append_child(&self.child.build(backend)?)?;
 
-        self.html_element = Some(element.clone());
-        self.render(element, document)
+        self.mount(element.clone());
+        Ok(element)
     }
 }
 
-pub struct Column {
+// Stack layers Positioned children over the others, e.g. a badge over an avatar or
+// a veil over a form that is loading. Children that are not positioned are laid out
+// one under the other and give the stack its size, later children are drawn on top.
+pub struct Stack {
     children: Vec<Box<dyn Viewable>>,
     styles: HashMap<&'static str, Box<dyn Style>>,
-    html_element: Option<web_sys::HtmlElement>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
 }
 
-impl Column {
-    pub fn new() -> Column {
-        Column {
+impl Stack {
+    pub fn new() -> Stack {
+        Stack {
             children: Vec::default(),
             styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
             html_element: None,
-        }.apply(&|mut column| {
-            column
-                .style(ContainerDirection::column())
-                .style(Justify::start());
-            return column;
+        }.apply(&|mut stack| {
+            stack.style(Position::relative());
+            return stack;
         })
     }
 
-    pub fn child(mut self, child: impl Viewable + 'static) -> Column {
+    pub fn child(mut self, child: impl Viewable + 'static) -> Stack {
         self.children.push(Box::new(child));
         self
     }
 
-    pub fn apply(self, f: &dyn Fn(Column) -> Column) -> Self {
+    pub fn apply(self, f: &dyn Fn(Stack) -> Stack) -> Self {
         f(self)
     }
 }
 
-impl Styleable for Column {
+impl Default for Stack {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+impl Styleable for Stack {
     fn store_style(&mut self, style: impl Style + 'static) {
         self.styles.insert(style.name(), Box::new(style));
     }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
 }
 
-impl Viewable for Column {
-    fn get_html_element(&mut self) -> Option<&web_sys::HtmlElement> {
+impl Listenable for Stack {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Stack {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for Stack {
+    fn get_html_element(&mut self) -> Option<&Element> {
         match &self.html_element {
             Some(e) => { Some(e) }
             None => None
         }
     }
 
-    fn render(&mut self, element: web_sys::HtmlElement, document: &web_sys::Document) -> Result<web_sys::HtmlElement, Error> {
-        for (_, mut style) in &self.styles {
-            style.build(&element)?;
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> {
+        self.children.iter_mut().collect()
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
+
+        for binding in &mut self.bindings {
+            binding.attach(&element);
         }
 
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
         for view in &mut self.children {
-            element.append_child(&view.build(document)?.dyn_into::<web_sys::Node>().unwrap());
+            element.append_child(&view.build(backend)?)?;
         }
 
-        self.html_element = Some(element.clone());
+        self.mount(element.clone());
         Ok(element)
     }
 }
 
-pub struct Row {
-    children: Vec<Box<dyn Viewable>>,
+// Positioned places its child in a Stack by offsets from the sides of the stack.
+// A side without an offset follows the size of the child.
+pub struct Positioned {
+    child: Box<dyn Viewable>,
+    // Offsets set so far, each side method adds to them
+    inset: Edge,
     styles: HashMap<&'static str, Box<dyn Style>>,
-    html_element: Option<web_sys::HtmlElement>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
 }
 
-impl Row {
-    pub fn new() -> Row {
-        Row {
-            children: Vec::default(),
+impl Positioned {
+    pub fn new(child: impl Viewable + 'static) -> Positioned {
+        Positioned {
+            child: Box::new(child),
+            inset: Edge { top: None, right: None, bottom: None, left: None },
             styles: Default::default(),
+            scoped_styles: Default::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
             html_element: None,
-        }
-            .apply(&|mut row| {
-                row
-                    .style(ContainerDirection::row())
-                    .style(Justify::start());
-                return row;
-            })
+        }.apply(&|mut positioned| {
+            positioned.style(Position::absolute());
+            return positioned;
+        })
     }
 
-    pub fn child(mut self, child: impl Viewable + 'static) -> Row {
-        self.children.push(Box::new(child));
+    // Covers the whole stack
+    pub fn fill(child: impl Viewable + 'static) -> Positioned {
+        Positioned::new(child).set_inset(Edge::all(Size::Pixel(0.0)))
+    }
+
+    pub fn top(self, offset: Size) -> Positioned {
+        let inset = self.inset.clone().top(offset);
+        self.set_inset(inset)
+    }
+
+    pub fn right(self, offset: Size) -> Positioned {
+        let inset = self.inset.clone().right(offset);
+        self.set_inset(inset)
+    }
+
+    pub fn bottom(self, offset: Size) -> Positioned {
+        let inset = self.inset.clone().bottom(offset);
+        self.set_inset(inset)
+    }
+
+    pub fn left(self, offset: Size) -> Positioned {
+        let inset = self.inset.clone().left(offset);
+        self.set_inset(inset)
+    }
+
+    fn set_inset(mut self, inset: Edge) -> Positioned {
+        self.inset = inset.clone();
+        self.style(Inset::edges(inset));
         self
     }
 
-    pub fn apply(self, f: &dyn Fn(Row) -> Row) -> Self {
+    // Draws the child above siblings with a lower index
+    pub fn z_index(mut self, index: i32) -> Positioned {
+        self.style(ZIndex::new(index));
+        self
+    }
+
+    pub fn apply(self, f: &dyn Fn(Positioned) -> Positioned) -> Self {
         f(self)
     }
 }
 
-impl Styleable for Row {
+impl Styleable for Positioned {
     fn store_style(&mut self, style: impl Style + 'static) {
         self.styles.insert(style.name(), Box::new(style));
     }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
 }
 
-impl Viewable for Row {
-    fn get_html_element(&mut self) -> Option<&web_sys::HtmlElement> {
+impl Listenable for Positioned {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Positioned {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+}
+
+impl Viewable for Positioned {
+    fn get_html_element(&mut self) -> Option<&Element> {
         match &self.html_element {
             Some(e) => { Some(e) }
             None => None
         }
     }
 
-    fn render(&mut self, element: web_sys::HtmlElement, document: &web_sys::Document) -> Result<web_sys::HtmlElement, Error> {
-        for (_, mut style) in &self.styles {
-            style.build(&element)?;
+    fn get_children(&mut self) -> Vec<&mut Box<dyn Viewable>> {
+        vec![&mut self.child]
+    }
+
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
         }
 
-        for view in &mut self.children {
-            element.append_child(&view.build(document)?.dyn_into::<web_sys::Node>().unwrap());
+        for binding in &mut self.bindings {
+            binding.attach(&element);
         }
 
-        self.html_element = Some(element.clone());
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        element.append_child(&self.child.build(backend)?)?;
+
+        self.mount(element.clone());
         Ok(element)
     }
 }
 
 pub struct Text {
-    str: &'static str,
+    str: Cow<'static, str>,
     styles: HashMap<&'static str, Box<dyn Style>>,
-    html_element: Option<web_sys::HtmlElement>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
 }
 
 impl Text {
-    pub fn new(str: &'static str) -> Text {
-        Text { str, styles: Default::default(), html_element: None }
+    // str is a literal or an owned String, e.g. Text::new(format!("{} unread", count))
+    pub fn new(str: impl Into<Cow<'static, str>>) -> Text {
+        Text { str: str.into(), styles: Default::default(), scoped_styles: Default::default(), listeners: Vec::default(), bindings: Vec::default(), html_element: None }
+    }
+
+    pub fn bind_text(&mut self, text: impl Fn() -> String + 'static) -> &mut Self {
+        self.bind(move |e| e.set_text(&text()))
     }
 
     pub fn apply(self, f: &dyn Fn(Text) -> Text) -> Self {
@@ -216,93 +1487,136 @@ impl Styleable for Text {
     fn store_style(&mut self, style: impl Style + 'static) {
         self.styles.insert(style.name(), Box::new(style));
     }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Text {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Text {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
 }
 
 impl Viewable for Text {
     fn get_tag(&self) -> &'static str { return "p"; }
 
-    fn get_html_element(&mut self) -> Option<&web_sys::HtmlElement> {
+    fn get_html_element(&mut self) -> Option<&Element> {
         match &self.html_element {
             Some(e) => { Some(e) }
             None => None
         }
     }
 
-    fn render(&mut self, element: web_sys::HtmlElement, _: &web_sys::Document) -> Result<web_sys::HtmlElement, Error> {
-        element.set_text_content(Some(&self.str));
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
 
-        for (_, mut style) in &self.styles {
-            style.build(&element)?;
+        for binding in &mut self.bindings {
+            binding.attach(&element);
         }
 
-        self.html_element = Some(element.clone());
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        element.set_text(&self.str);
+
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        self.mount(element.clone());
         Ok(element)
     }
 }
 
 pub struct Button {
-    str: &'static str,
+    str: Cow<'static, str>,
     styles: HashMap<&'static str, Box<dyn Style>>,
-    html_element: Option<web_sys::HtmlElement>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
 }
 
 impl Button {
-    pub fn new(str: &'static str) -> Button {
+    pub fn new(str: impl Into<Cow<'static, str>>) -> Button {
         Button::primary(str)
     }
 
-    fn prepare(str: &'static str) -> Button {
+    fn prepare(str: impl Into<Cow<'static, str>>) -> Button {
         Button {
-            str,
+            str: str.into(),
             styles: HashMap::default(),
+            scoped_styles: HashMap::default(),
+            listeners: Vec::default(),
+            bindings: Vec::default(),
             html_element: None,
         }
             .apply(&|mut button| {
+                let theme = Theme::current();
                 button
-                    .style(Color::new(Colors::White))
+                    .style(Color::new(theme.palette().on_primary))
                     .style(Cursor::pointer())
                     .style(BorderStyle::none())
-                    .style(FontSize::new(Size::Pixel(14.0)))
-                    .style(FontFamily::new(Font::SansSerif))
+                    .style(FontSize::new(theme.typography.button.clone()))
+                    .style(FontFamily::new(theme.typography.family.clone()))
                     .style(TextTransform::uppercase())
-                    .style(BorderRadius::new(Size::Pixel(5.0)))
-                    .style(Padding::block(Size::Pixel(10.0)))
-                    .style(Padding::inline(Size::Pixel(45.0)))
-                    .style(FontWeight::new("500"));
+                    .style(BorderRadius::new(theme.radius.sm.clone()))
+                    .style(Padding::block(theme.spacing.sm.clone()))
+                    .style(Padding::inline(theme.spacing.xl.clone()))
+                    .style(FontWeight::new(theme.typography.button_weight));
                 return button;
             })
     }
 
-    pub fn primary(str: &'static str) -> Button {
+    pub fn primary(str: impl Into<Cow<'static, str>>) -> Button {
         Button::prepare(str)
             .apply(&|mut button| {
+                let theme = Theme::current();
+                let palette = theme.palette();
                 button
-                    .style(Background::color(Colors::Custom("#6979F8")));
+                    .style(Background::color(palette.primary))
+                    .style_on(State::Hover, Background::color(theme.emphasize(&palette.primary, 0.05)))
+                    .style_on(State::Active, Background::color(theme.emphasize(&palette.primary, 0.1)));
                 return button;
             })
     }
 
-    pub fn text(str: &'static str) -> Button {
+    pub fn text(str: impl Into<Cow<'static, str>>) -> Button {
         Button::prepare(str)
             .apply(&|mut button| {
                 button
-                    .style(Color::new(Colors::Custom("#6979F8")))
+                    .style(Color::new(Theme::current().palette().primary))
                     .style(Background::color(Colors::None));
                 return button;
             })
     }
 
-    pub fn disabled(str: &'static str) -> Button {
+    pub fn disabled(str: impl Into<Cow<'static, str>>) -> Button {
         Button::prepare(str)
             .apply(&|mut button| {
+                let theme = Theme::current();
+                let palette = theme.palette();
                 button
-                    .style(Color::new(Colors::Lightgray))
+                    .style(Color::new(palette.disabled))
                     .style(Cursor::default())
-                    .style(Background::color(Colors::Custom("#FBE4E8")));
+                    .style(Background::color(palette.error_container));
                 return button;
             })
     }
 
+    pub fn bind_text(&mut self, text: impl Fn() -> String + 'static) -> &mut Self {
+        self.bind(move |e| e.set_text(&text()))
+    }
+
     pub fn apply(self, f: &dyn Fn(Button) -> Button) -> Self {
         f(self)
     }
@@ -312,54 +1626,99 @@ impl Styleable for Button {
     fn store_style(&mut self, style: impl Style + 'static) {
         self.styles.insert(style.name(), Box::new(style));
     }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Button {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Button {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
 }
 
 impl Viewable for Button {
     fn get_tag(&self) -> &'static str { return "button"; }
 
-    fn get_html_element(&mut self) -> Option<&web_sys::HtmlElement> {
+    fn get_html_element(&mut self) -> Option<&Element> {
         match &self.html_element {
             Some(e) => { Some(e) }
             None => None
         }
     }
 
-    fn render(&mut self, element: web_sys::HtmlElement, _: &web_sys::Document) -> Result<web_sys::HtmlElement, Error> {
-        element.set_text_content(Some(&self.str));
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
 
-        for (_, mut style) in &self.styles {
-            // style.build(&*Rc::clone(&btn))?;
-            style.build(&element)?;
+        for binding in &mut self.bindings {
+            binding.attach(&element);
         }
 
-        self.html_element = Some(element.clone());
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        element.set_text(&self.str);
+
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        self.mount(element.clone());
         Ok(element)
     }
 }
 
 pub struct Link {
-    str: &'static str,
-    address: &'static str,
+    str: Cow<'static, str>,
+    address: Cow<'static, str>,
     styles: HashMap<&'static str, Box<dyn Style>>,
-    html_element: Option<web_sys::HtmlAnchorElement>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
 }
 
 impl Link {
-    pub fn new(str: &'static str, address: &'static str) -> Link {
-        Link { str, address, styles: Default::default(), html_element: None }
+    pub fn new(str: impl Into<Cow<'static, str>>, address: impl Into<Cow<'static, str>>) -> Link {
+        let address = address.into();
+        Link { str: str.into(), address: address.clone(), styles: Default::default(), scoped_styles: Default::default(), listeners: Vec::default(), bindings: Vec::default(), html_element: None }
             // Disabled Style
             // Color: #D0C9D6
             // Cursor: default
             .apply(&|mut link| {
-                link.style(Color::new(Colors::Custom("#3F3356")))
+                let theme = Theme::current();
+                link.style(Color::new(theme.palette().link))
                     .style(TextDecoration::none())
-                    .style(FontSize::new(Size::Pixel(16.0)))
-                    .style(FontFamily::new(Font::SansSerif))
-                    .style(Cursor::pointer());
+                    .style(FontSize::new(theme.typography.body.clone()))
+                    .style(FontFamily::new(theme.typography.family.clone()))
+                    .style(Cursor::pointer())
+                    // Followed by the router when the window has one. Clicks with
+                    // a modifier or another button are left to the browser, e.g. to
+                    // open the link in a new tab.
+                    .on_click({
+                        let address = address.clone();
+                        move |e| {
+                            if e.is_plain_click() && router::navigate(&address) {
+                                e.prevent_default();
+                            }
+                        }
+                    });
                 return link;
             })
     }
 
+    pub fn bind_text(&mut self, text: impl Fn() -> String + 'static) -> &mut Self {
+        self.bind(move |e| e.set_text(&text()))
+    }
+
     pub fn apply(self, f: &dyn Fn(Link) -> Link) -> Self {
         f(self)
     }
@@ -369,67 +1728,105 @@ impl Styleable for Link {
     fn store_style(&mut self, style: impl Style + 'static) {
         self.styles.insert(style.name(), Box::new(style));
     }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Link {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Link {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
 }
 
 impl Viewable for Link {
     fn get_tag(&self) -> &'static str { return "a"; }
 
-    fn get_html_element(&mut self) -> Option<&web_sys::HtmlElement> {
+    fn get_html_element(&mut self) -> Option<&Element> {
         match &self.html_element {
             Some(e) => { Some(e) }
             None => None
         }
     }
 
-    fn render(&mut self, element: web_sys::HtmlElement, _: &web_sys::Document) -> Result<web_sys::HtmlElement, Error> {
-        let l = element.dyn_into::<web_sys::HtmlAnchorElement>().unwrap();
-
-        l.set_text_content(Some(&self.str));
-        l.set_href(self.address);
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
 
-        for (_, mut style) in &self.styles {
-            style.build(&l)?;
+        for binding in &mut self.bindings {
+            binding.attach(&element);
         }
 
-        self.html_element = Some(l.clone());
-        Ok(l.deref().clone())
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        element.set_text(&self.str);
+        element.set_attribute("href", &self.address)?;
+
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        self.mount(element.clone());
+        Ok(element)
     }
 }
 
 pub struct Label {
-    str: &'static str,
+    str: Cow<'static, str>,
+    enabled: bool,
     styles: HashMap<&'static str, Box<dyn Style>>,
-    html_element: Option<web_sys::HtmlElement>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
 }
 
 impl Label {
-    pub fn new(str: &'static str) -> Label {
-        Label { str, styles: Default::default(), html_element: None }
+    pub fn new(str: impl Into<Cow<'static, str>>) -> Label {
+        Label { str: str.into(), enabled: true, styles: Default::default(), scoped_styles: Default::default(), listeners: Vec::default(), bindings: Vec::default(), html_element: None }
             .apply(&|mut label| {
+                let theme = Theme::current();
                 label
-                    .style(FontSize::new(Size::Pixel(16.0)))
+                    .style(FontSize::new(theme.typography.body.clone()))
                     .style(TextTransform::uppercase())
-                    .style(FontFamily::new(Font::SansSerif))
-                    .style(BorderRadius::new(Size::Pixel(5.0)))
-                    .style(Padding::block(Size::Pixel(8.0)))
-                    .style(Padding::inline(Size::Pixel(14.0)))
+                    .style(FontFamily::new(theme.typography.family.clone()))
+                    .style(BorderRadius::new(theme.radius.sm.clone()))
+                    .style(Padding::block(theme.spacing.xs.clone()))
+                    .style(Padding::inline(theme.spacing.md.clone()))
                     .enable(true);
                 return label;
             })
     }
 
+    // A label has no disabled attribute, so assistive technology is told with aria-disabled
     pub fn enable(&mut self, is: bool) -> &Self {
+        self.enabled = is;
+        self.set(Box::new(move |e| report(toggle_attribute(e, "aria-disabled", "true", !is))));
+        let theme = Theme::current();
+        let palette = theme.palette();
         if is {
             self
-                .style(Color::new(Colors::White))
-                .style(Background::color(Colors::Custom("#BE52F2")))
+                .style(Color::new(palette.on_secondary))
+                .style(Background::color(palette.secondary))
         } else {
             self
-                .style(Color::new(Colors::Custom("#BE52F2")))
-                .style(Background::color(Colors::Custom("#EEDFF2")))
+                .style(Color::new(palette.secondary))
+                .style(Background::color(palette.secondary.mix(&palette.background, 0.85)))
         }
     }
 
+    pub fn bind_text(&mut self, text: impl Fn() -> String + 'static) -> &mut Self {
+        self.bind(move |e| e.set_text(&text()))
+    }
+
     pub fn apply(self, f: &dyn Fn(Label) -> Label) -> Self {
         f(self)
     }
@@ -439,51 +1836,84 @@ impl Styleable for Label {
     fn store_style(&mut self, style: impl Style + 'static) {
         self.styles.insert(style.name(), Box::new(style));
     }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Label {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Label {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
 }
 
 impl Viewable for Label {
-    fn get_html_element(&mut self) -> Option<&web_sys::HtmlElement> {
+    fn get_html_element(&mut self) -> Option<&Element> {
         match &self.html_element {
             Some(e) => { Some(e) }
             None => None
         }
     }
 
-    fn render(&mut self, element: web_sys::HtmlElement, _: &web_sys::Document) -> Result<web_sys::HtmlElement, Error> {
-        element.set_text_content(Some(&self.str));
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
 
-        for (_, mut style) in &self.styles {
-            // style.build(&*Rc::clone(&btn))?;
-            style.build(&element)?;
+        for binding in &mut self.bindings {
+            binding.attach(&element);
         }
 
-        self.html_element = Some(element.clone());
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        element.set_text(&self.str);
+        toggle_attribute(&element, "aria-disabled", "true", !self.enabled)?;
+
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        self.mount(element.clone());
         Ok(element)
     }
 }
 
 pub struct Input {
-    pub placeholder: &'static str,
+    pub placeholder: Cow<'static, str>,
     pub enabled: bool,
     styles: HashMap<&'static str, Box<dyn Style>>,
-    html_element: Option<web_sys::HtmlInputElement>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
 }
 
 impl Input {
-    pub fn new(placeholder: &'static str) -> Input {
-        Input { placeholder, enabled: true, styles: Default::default(), html_element: None }
+    pub fn new(placeholder: impl Into<Cow<'static, str>>) -> Input {
+        Input { placeholder: placeholder.into(), enabled: true, styles: Default::default(), scoped_styles: Default::default(), listeners: Vec::default(), bindings: Vec::default(), html_element: None }
             .apply(&|mut input| {
+                let theme = Theme::current();
                 input
                     .style(Width::new(Size::Pixel(210.0)))
                     .style(Height::new(Size::Pixel(40.0)))
-                    .style(FontSize::new(Size::Pixel(15.0)))
+                    .style(FontSize::new(theme.typography.input.clone()))
                     .style(TextTransform::capitalize())
-                    .style(FontFamily::new(Font::SansSerif))
-                    .style(BorderRadius::new(Size::Pixel(5.0)))
-                    .style(Padding::left(Size::Pixel(14.0)))
+                    .style(FontFamily::new(theme.typography.family.clone()))
+                    .style(BorderRadius::new(theme.radius.sm.clone()))
+                    .style(Padding::left(theme.spacing.md.clone()))
                     .style(BorderStyle::solid())
                     .style(BorderWidth::new(Size::Pixel(2.0)))
-                    .style(BorderColor::new(Colors::Custom("#ECE9F1")))
+                    .style(BorderColor::new(theme.palette().border))
+                    .style_on(State::Focus, BorderColor::new(theme.palette().primary))
+                    .style_on(State::Focus, Outline::none())
+                    .style_on(State::Disabled, Cursor::default())
                     .enable(true);
                 return input;
             })
@@ -491,17 +1921,24 @@ impl Input {
 
     pub fn enable(&mut self, is: bool) -> &Self {
         self.enabled = is;
+        self.set(Box::new(move |e| report(toggle_attribute(e, "disabled", "", !is))));
+        let theme = Theme::current();
+        let palette = theme.palette();
         if is {
             self
-                .style(Color::new(Colors::Custom("#1A051D")))
-                .style(Background::color(Colors::White))
+                .style(Color::new(palette.text))
+                .style(Background::color(palette.surface))
         } else {
             self
-                .style(Color::new(Colors::Custom("#D0C9D6")))
-                .style(Background::color(Colors::Custom("#ECE9F1")))
+                .style(Color::new(palette.disabled))
+                .style(Background::color(palette.disabled_container))
         }
     }
 
+    pub fn bind_enabled(&mut self, enabled: impl Fn() -> bool + 'static) -> &mut Self {
+        self.bind(move |e| report(toggle_attribute(e, "disabled", "", !enabled())))
+    }
+
     pub fn apply(self, f: &dyn Fn(Input) -> Input) -> Self {
         f(self)
     }
@@ -511,52 +1948,83 @@ impl Styleable for Input {
     fn store_style(&mut self, style: impl Style + 'static) {
         self.styles.insert(style.name(), Box::new(style));
     }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Input {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Input {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
 }
 
 impl Viewable for Input {
     fn get_tag(&self) -> &'static str { return "input"; }
 
-    fn get_html_element(&mut self) -> Option<&web_sys::HtmlElement> {
+    fn get_html_element(&mut self) -> Option<&Element> {
         match &self.html_element {
             Some(e) => { Some(e) }
             None => None
         }
     }
 
-    fn render(&mut self, element: web_sys::HtmlElement, _: &web_sys::Document) -> Result<web_sys::HtmlElement, Error> {
-        let input = element.dyn_into::<web_sys::HtmlInputElement>().unwrap();
-
-        // input.set_value(self.str);
-        input.set_placeholder(self.placeholder);
-        input.set_disabled(!self.enabled);
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
 
-        for (_, mut style) in &self.styles {
-            style.build(&input)?;
+        for binding in &mut self.bindings {
+            binding.attach(&element);
         }
 
-        self.html_element = Some(input.clone());
-        Ok(input.deref().clone())
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        element.set_attribute("placeholder", &self.placeholder)?;
+        toggle_attribute(&element, "disabled", "", !self.enabled)?;
+
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        self.mount(element.clone());
+        Ok(element)
     }
 }
 
 pub struct Title {
-    str: &'static str,
+    str: Cow<'static, str>,
     tag: &'static str,
     styles: HashMap<&'static str, Box<dyn Style>>,
-    html_element: Option<web_sys::HtmlElement>,
+    scoped_styles: HashMap<(Scope, &'static str), Box<dyn Style>>,
+    listeners: Vec<Listener>,
+    bindings: Vec<Binding>,
+    html_element: Option<Element>,
 }
 
 impl Title {
-    pub fn h2(str: &'static str) -> Title {
-        Title { str, tag: "h2", styles: Default::default(), html_element: None }
+    pub fn h2(str: impl Into<Cow<'static, str>>) -> Title {
+        Title { str: str.into(), tag: "h2", styles: Default::default(), scoped_styles: Default::default(), listeners: Vec::default(), bindings: Vec::default(), html_element: None }
             .apply(&|mut title| {
-                title.style(Color::new(Colors::Black))
-                    .style(FontSize::new(Size::Pixel(22.0)))
-                    .style(FontFamily::new(Font::SansSerif));
+                let theme = Theme::current();
+                title.style(Color::new(theme.palette().heading))
+                    .style(FontSize::new(theme.typography.heading.clone()))
+                    .style(FontFamily::new(theme.typography.family.clone()));
                 return title;
             })
     }
 
+    pub fn bind_text(&mut self, text: impl Fn() -> String + 'static) -> &mut Self {
+        self.bind(move |e| e.set_text(&text()))
+    }
+
     pub fn apply(self, f: &dyn Fn(Title) -> Title) -> Self {
         f(self)
     }
@@ -566,26 +2034,349 @@ impl Styleable for Title {
     fn store_style(&mut self, style: impl Style + 'static) {
         self.styles.insert(style.name(), Box::new(style));
     }
+
+    fn store_scoped_style(&mut self, scope: Scope, style: impl Style + 'static) {
+        self.scoped_styles.insert((scope, style.name()), Box::new(style));
+    }
+}
+
+impl Listenable for Title {
+    fn store_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl Bindable for Title {
+    fn store_binding(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
 }
 
 impl Viewable for Title {
     fn get_tag(&self) -> &'static str { return self.tag; }
 
-    fn get_html_element(&mut self) -> Option<&web_sys::HtmlElement> {
+    fn get_html_element(&mut self) -> Option<&Element> {
         match &self.html_element {
             Some(e) => { Some(e) }
             None => None
         }
     }
 
-    fn render(&mut self, element: web_sys::HtmlElement, _: &web_sys::Document) -> Result<web_sys::HtmlElement, Error> {
-        element.set_text_content(Some(&self.str));
+    fn mount(&mut self, element: Element) {
+        for listener in &mut self.listeners {
+            listener.attach(&element);
+        }
 
-        for (_, mut style) in &self.styles {
-            style.build(&element)?;
+        for binding in &mut self.bindings {
+            binding.attach(&element);
         }
 
-        self.html_element = Some(element.clone());
+        self.html_element = Some(element);
+    }
+
+    fn render(&mut self, element: Element, backend: &dyn Backend) -> Result<Element, Error> {
+        element.set_text(&self.str);
+
+        stylesheet::apply(&self.styles, &self.scoped_styles, &element, backend)?;
+
+        self.mount(element.clone());
         Ok(element)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use crate::backend::memory::{MemoryBackend, MemoryNode};
+    use crate::view::color::Srgb;
+    use crate::view::shadow::Shadow;
+    use crate::view::size::{Edge, Size};
+    use crate::view::style::*;
+    use crate::view::view::Viewable;
+    use crate::view::grid::{Line, Template, Track};
+    use crate::view::widget::{AspectRatio, Center, Column, Container, Expanded, Grid, Input, Label, Positioned, Row,
+                              SizedBox, Spacer, Stack, Styleable, Text};
+
+    // Styles of the built view and of each of its children
+    fn styles(view: &mut dyn Viewable) -> (BTreeMap<String, String>, Vec<BTreeMap<String, String>>) {
+        let backend = MemoryBackend::new();
+        let element = view.build(&backend).unwrap();
+        let node = MemoryNode::of(&element).unwrap();
+        let children = node.children().iter()
+            .map(|child| MemoryNode::of(child).unwrap().styles())
+            .collect();
+        (node.styles(), children)
+    }
+
+    fn style<'a>(styles: &'a BTreeMap<String, String>, name: &str) -> Option<&'a str> {
+        styles.get(name).map(String::as_str)
+    }
+
+    #[test]
+    fn row_is_a_flex_row() {
+        let (row, _) = styles(&mut Row::new());
+        assert_eq!(style(&row, "display"), Some("flex"));
+        assert_eq!(style(&row, "flex-direction"), Some("row"));
+        assert_eq!(style(&row, "justify-content"), Some("flex-start"));
+    }
+
+    #[test]
+    fn column_is_a_flex_column() {
+        let (column, _) = styles(&mut Column::new());
+        assert_eq!(style(&column, "display"), Some("flex"));
+        assert_eq!(style(&column, "flex-direction"), Some("column"));
+        assert_eq!(style(&column, "justify-content"), Some("flex-start"));
+    }
+
+    #[test]
+    fn gap_and_wrap() {
+        let (row, _) = styles(&mut Row::new().gap(Size::Pixel(8.0)).wrap());
+        assert_eq!(style(&row, "gap"), Some("8px"));
+        assert_eq!(style(&row, "flex-wrap"), Some("wrap"));
+
+        let (column, _) = styles(&mut Column::new().gap(Size::Rem(1.0)));
+        assert_eq!(style(&column, "gap"), Some("1rem"));
+        assert_eq!(style(&column, "flex-wrap"), None);
+    }
+
+    #[test]
+    fn align_and_justify_the_container() {
+        let mut row = Row::new().apply(&|mut row| {
+            row.style(Justify::space_between()).style(Align::center());
+            return row;
+        });
+        let (row, _) = styles(&mut row);
+        assert_eq!(style(&row, "justify-content"), Some("space-between"));
+        assert_eq!(style(&row, "align-items"), Some("center"));
+    }
+
+    #[test]
+    fn children_grow_shrink_and_align_themselves() {
+        let mut row = Row::new()
+            .child(Text::new("fixed").apply(&|mut text| {
+                text.style(FlexShrink::new(0.0)).style(FlexBasis::new(Size::Pixel(120.0)));
+                return text;
+            }))
+            .child(Text::new("fill").apply(&|mut text| {
+                text.style(FlexGrow::new(1.0)).style(AlignSelf::end());
+                return text;
+            }));
+        let (_, children) = styles(&mut row);
+
+        assert_eq!(style(&children[0], "flex-shrink"), Some("0"));
+        assert_eq!(style(&children[0], "flex-basis"), Some("120px"));
+        assert_eq!(style(&children[0], "flex-grow"), None);
+        assert_eq!(style(&children[1], "flex-grow"), Some("1"));
+        assert_eq!(style(&children[1], "align-self"), Some("flex-end"));
+    }
+
+    #[test]
+    fn flex_styles_round_trip() {
+        assert_eq!("flex-grow: 2.5".parse::<FlexGrow>().unwrap().to_string(), "flex-grow: 2.5");
+        assert_eq!("flex-basis: 25%".parse::<FlexBasis>().unwrap().to_string(), "flex-basis: 25%");
+        assert_eq!("align-self: baseline".parse::<AlignSelf>().unwrap().to_string(), "align-self: baseline");
+        assert_eq!("flex-wrap: wrap-reverse".parse::<FlexWrap>().unwrap().to_string(), "flex-wrap: wrap-reverse");
+        assert!("flex-shrink: -1".parse::<FlexShrink>().is_err());
+        assert_eq!(FlexGrow::new(f32::INFINITY).to_string(), "flex-grow: 0");
+        assert_eq!(FlexGrow::new(f32::NAN).to_string(), "flex-grow: 0");
+        assert_eq!(FlexShrink::new(f32::NEG_INFINITY).to_string(), "flex-shrink: 0");
+        assert_eq!(FlexShrink::new(-1.0).to_string(), "flex-shrink: 0");
+        assert!("display: table".parse::<DisplayMode>().is_err());
+    }
+
+    #[test]
+    fn spacer_and_expanded_share_the_free_space() {
+        let mut row = Row::new()
+            .child(Spacer::new())
+            .child(Expanded::flex(2.0, Text::new("wide")));
+        let (_, children) = styles(&mut row);
+
+        assert_eq!(style(&children[0], "flex-grow"), Some("1"));
+        assert_eq!(style(&children[0], "flex-basis"), Some("0px"));
+        assert_eq!(style(&children[1], "flex-grow"), Some("2"));
+        assert_eq!(style(&children[1], "flex-basis"), Some("0px"));
+        assert_eq!(style(&children[1], "min-width"), Some("0px"));
+        assert_eq!(style(&children[1], "min-height"), Some("0px"));
+    }
+
+    #[test]
+    fn boxes_size_and_center_their_child() {
+        let mut column = Column::new()
+            .child(SizedBox::new(Size::Pixel(40.0), Size::Pixel(20.0)))
+            .child(SizedBox::height(Size::Pixel(8.0)).child(Text::new("inside")))
+            .child(Center::new(Text::new("centered")))
+            .child(AspectRatio::new(1.5, Text::new("photo")))
+            .child(AspectRatio::new(-1.0, Text::new("broken")));
+        let (_, children) = styles(&mut column);
+
+        assert_eq!(style(&children[0], "width"), Some("40px"));
+        assert_eq!(style(&children[0], "height"), Some("20px"));
+        assert_eq!(style(&children[0], "flex-shrink"), Some("0"));
+        assert_eq!(style(&children[1], "width"), None);
+        assert_eq!(style(&children[2], "display"), Some("flex"));
+        assert_eq!(style(&children[2], "justify-content"), Some("center"));
+        assert_eq!(style(&children[2], "align-items"), Some("center"));
+        assert_eq!(style(&children[3], "aspect-ratio"), Some("1.5 / 1"));
+        assert_eq!(style(&children[3], "width"), Some("100%"));
+        assert_eq!(style(&children[4], "aspect-ratio"), Some("auto"));
+    }
+
+    #[test]
+    fn enabling_a_mounted_widget_updates_its_element() {
+        let backend = MemoryBackend::new();
+        let mut input = Input::new("Name");
+        let element = input.build(&backend).unwrap();
+        let node = MemoryNode::of(&element).unwrap();
+        assert_eq!(node.attribute("disabled"), None);
+
+        input.enable(false);
+        assert_eq!(node.attribute("disabled").as_deref(), Some(""));
+        assert_eq!(node.style("color").as_deref(), Some("#D0C9D6"));
+        input.enable(true);
+        assert_eq!(node.attribute("disabled"), None);
+        assert_eq!(node.style("color").as_deref(), Some("#1A051D"));
+
+        let mut label = Label::new("Name");
+        label.enable(false);
+        let element = label.build(&backend).unwrap();
+        let node = MemoryNode::of(&element).unwrap();
+        assert_eq!(node.attribute("aria-disabled").as_deref(), Some("true"));
+        label.enable(true);
+        assert_eq!(node.attribute("aria-disabled"), None);
+    }
+
+    #[test]
+    fn container_draws_a_box_around_its_child() {
+        let mut container = Container::new(Text::new("card"))
+            .padding(Edge::symmetric(Size::Pixel(8.0), Size::Pixel(16.0)))
+            .margin(Edge::all(Size::Auto))
+            .border(Size::Pixel(1.0), Srgb::rgb(0xEC, 0xE9, 0xF1))
+            .radius(Size::Pixel(10.0))
+            .background(Srgb::rgb(0xFF, 0xFF, 0xFF))
+            .shadow(Shadow::new(Size::Pixel(0.0), Size::Pixel(2.0), Size::Pixel(4.0), Srgb::rgb(0, 0, 0)))
+            .max_width(Size::Pixel(480.0));
+        let (container, children) = styles(&mut container);
+
+        assert_eq!(children.len(), 1);
+        assert_eq!(style(&container, "padding-top"), Some("8px"));
+        assert_eq!(style(&container, "padding-right"), Some("16px"));
+        assert_eq!(style(&container, "padding-bottom"), Some("8px"));
+        assert_eq!(style(&container, "padding-left"), Some("16px"));
+        assert_eq!(style(&container, "margin-left"), Some("auto"));
+        assert_eq!(style(&container, "border-style"), Some("solid"));
+        assert_eq!(style(&container, "border-width"), Some("1px"));
+        assert_eq!(style(&container, "border-color"), Some("#ECE9F1"));
+        assert_eq!(style(&container, "border-radius"), Some("10px"));
+        assert_eq!(style(&container, "background"), Some("#FFFFFF"));
+        assert_eq!(style(&container, "box-shadow"), Some("0px 2px 4px 0px #000000"));
+        assert_eq!(style(&container, "max-width"), Some("480px"));
+    }
+
+    #[test]
+    fn grid_templates_areas_and_spans() {
+        let mut grid = Grid::new()
+            .columns(Template::tracks(vec![Size::Pixel(120.0).into(), Track::minmax(Size::Pixel(0.0), Size::Fr(1.0))]))
+            .rows(Template::repeat(2, Size::Auto))
+            .areas(vec![vec!["label", "input"], vec![".", "input"]])
+            .gap(Size::Pixel(8.0))
+            .child(Text::new("label").apply(&|mut text| {
+                text.style(GridArea::new("label"));
+                return text;
+            }))
+            .child(Text::new("wide").apply(&|mut text| {
+                text.style(GridColumn::span(2)).style(GridRow::new(Line::At(1), Line::At(-1)));
+                return text;
+            }));
+        let (grid, children) = styles(&mut grid);
+
+        assert_eq!(style(&grid, "display"), Some("grid"));
+        assert_eq!(style(&grid, "grid-template-columns"), Some("120px minmax(0px, 1fr)"));
+        assert_eq!(style(&grid, "grid-template-rows"), Some("repeat(2, auto)"));
+        assert_eq!(style(&grid, "grid-template-areas"), Some("\"label input\" \". input\""));
+        assert_eq!(style(&grid, "gap"), Some("8px"));
+        assert_eq!(style(&children[0], "grid-area"), Some("label"));
+        assert_eq!(style(&children[1], "grid-column"), Some("auto / span 2"));
+        assert_eq!(style(&children[1], "grid-row"), Some("1 / -1"));
+    }
+
+    #[test]
+    fn grid_fills_columns_that_fit() {
+        let template = Template::auto_fill(Track::minmax(Size::Pixel(240.0), Size::Fr(1.0)));
+        let (grid, _) = styles(&mut Grid::new().columns(template));
+        assert_eq!(style(&grid, "grid-template-columns"), Some("repeat(auto-fill, minmax(240px, 1fr))"));
+    }
+
+    #[test]
+    fn grid_gaps_add_up() {
+        let (grid, _) = styles(&mut Grid::new().gap(Size::Pixel(8.0)).row_gap(Size::Pixel(4.0)));
+        assert_eq!(style(&grid, "gap"), None);
+        assert_eq!(style(&grid, "row-gap"), Some("4px"));
+        assert_eq!(style(&grid, "column-gap"), Some("8px"));
+
+        let (grid, _) = styles(&mut Grid::new().column_gap(Size::Pixel(4.0)).row_gap(Size::Pixel(4.0)));
+        assert_eq!(style(&grid, "gap"), Some("4px"));
+        assert_eq!(style(&grid, "row-gap"), None);
+    }
+
+    #[test]
+    fn grid_styles_round_trip() {
+        for text in ["grid-template-columns: repeat(auto-fit, minmax(10rem, 1fr))",
+            "grid-template-rows: auto 1fr min-content",
+            "grid-template-areas: \"a a\" \"b .\"",
+            "grid-column: 2 / span 3",
+            "grid-row: auto / -1"] {
+            let parsed = match text.split(':').next().unwrap() {
+                "grid-template-areas" => text.parse::<GridTemplateAreas>().map(|s| s.to_string()),
+                "grid-column" => text.parse::<GridColumn>().map(|s| s.to_string()),
+                "grid-row" => text.parse::<GridRow>().map(|s| s.to_string()),
+                _ => text.parse::<GridTemplate>().map(|s| s.to_string()),
+            };
+            assert_eq!(parsed.unwrap(), text);
+        }
+        assert!("grid-template-areas: \"a b\" \"c\"".parse::<GridTemplateAreas>().is_err());
+        assert!("grid-column: span 0".parse::<GridColumn>().is_err());
+        assert!("grid-template-columns: repeat(0, 1fr)".parse::<GridTemplate>().is_err());
+    }
+
+    #[test]
+    fn stack_layers_positioned_children() {
+        let mut stack = Stack::new()
+            .child(Text::new("avatar"))
+            .child(Positioned::new(Text::new("3")).top(Size::Pixel(-4.0)).right(Size::Pixel(-4.0)).z_index(1))
+            .child(Positioned::fill(Text::new("loading")));
+        let (stack, children) = styles(&mut stack);
+
+        assert_eq!(style(&stack, "position"), Some("relative"));
+        assert_eq!(style(&children[0], "position"), None);
+        assert_eq!(style(&children[1], "position"), Some("absolute"));
+        assert_eq!(style(&children[1], "top"), Some("-4px"));
+        assert_eq!(style(&children[1], "right"), Some("-4px"));
+        assert_eq!(style(&children[1], "bottom"), None);
+        assert_eq!(style(&children[1], "z-index"), Some("1"));
+        assert_eq!(style(&children[2], "inset"), Some("0px"));
+    }
+
+    #[test]
+    fn positioned_merges_its_offsets() {
+        let mut stack = Stack::new().child(Positioned::fill(Text::new("bar")).bottom(Size::Pixel(8.0)));
+        let (_, children) = styles(&mut stack);
+
+        assert_eq!(style(&children[0], "inset"), None);
+        assert_eq!(style(&children[0], "top"), Some("0px"));
+        assert_eq!(style(&children[0], "right"), Some("0px"));
+        assert_eq!(style(&children[0], "bottom"), Some("8px"));
+        assert_eq!(style(&children[0], "left"), Some("0px"));
+    }
+
+    #[test]
+    fn position_styles_round_trip() {
+        assert_eq!("position: sticky".parse::<Position>().unwrap().to_string(), "position: sticky");
+        assert_eq!("top: 0px; left: 50%".parse::<Inset>().unwrap().to_string(), "top: 0px; left: 50%");
+        assert_eq!("z-index: -2".parse::<ZIndex>().unwrap().to_string(), "z-index: -2");
+        assert!("z-index: 1.5".parse::<ZIndex>().is_err());
+        assert_eq!("inset: 0px; bottom: 8px".parse::<Inset>().unwrap().to_string(),
+                   "top: 0px; right: 0px; bottom: 8px; left: 0px");
+        assert_eq!("top: 1px; right: 1px; bottom: 1px; left: 1px".parse::<Inset>().unwrap().to_string(), "inset: 1px");
+        assert!("margin-top: 1px".parse::<Inset>().is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,45 @@
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsValue;
+use crate::view::font::FontFace;
+use crate::view::reactive::Signal;
+use crate::view::view::Error;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FontStatus {
+    Loading,
+    Loaded,
+    Failed,
+}
+
+// load adds face to the document through the FontFace API and starts downloading it.
+// The returned signal turns Loaded once the font can be drawn, or Failed, so widgets
+// can bind to it, e.g. to hold back text that must not flash in a fallback font.
+pub fn load(face: &FontFace) -> Result<Signal<FontStatus>, Error> {
+    let failed = |_| Error::FontFace(face.family.to_string());
+    let document = web_sys::window().and_then(|w| w.document()).ok_or(failed(JsValue::NULL))?;
+
+    let descriptors = web_sys::FontFaceDescriptors::new();
+    descriptors.set_display(&face.display.to_string());
+    if let Some(weight) = face.weight {
+        descriptors.set_weight(weight);
+    }
+    if let Some(style) = face.style {
+        descriptors.set_style(style);
+    }
+
+    let font = web_sys::FontFace::new_with_str_and_descriptors(face.family, &face.src(), &descriptors)
+        .map_err(failed)?;
+    document.fonts().add(&font).map_err(failed)?;
+
+    let status = Signal::new(FontStatus::Loading);
+    let loaded = status.clone();
+    let rejected = status.clone();
+    let on_loaded = Closure::once(move |_: JsValue| loaded.set(FontStatus::Loaded));
+    let on_rejected = Closure::once(move |_: JsValue| rejected.set(FontStatus::Failed));
+    let _ = font.load().map_err(failed)?.then2(&on_loaded, &on_rejected);
+    // The promise settles once, after which the closures are not called again
+    on_loaded.forget();
+    on_rejected.forget();
+
+    Ok(status)
+}
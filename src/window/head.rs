@@ -0,0 +1,177 @@
+use crate::backend::backend::{Backend, Element};
+use crate::view::font::{self, FontFace};
+use crate::view::view::Error;
+
+// Attribute set on every tag a Head adds, so they can be told apart from the page's own
+pub const MARKER: &str = "data-head";
+
+struct Tag {
+    tag: &'static str,
+    attributes: Vec<(&'static str, String)>,
+    text: Option<String>,
+}
+
+// Head describes what a page puts into the document head.
+// The window adds these tags when the page is mounted and removes them when another page is.
+pub struct Head {
+    title: Option<String>,
+    tags: Vec<Tag>,
+}
+
+impl Head {
+    pub fn new() -> Head {
+        Head { title: None, tags: Vec::default() }
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Head {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn meta(self, name: &'static str, content: impl Into<String>) -> Head {
+        self.tag("meta", vec![("name", name.to_string()), ("content", content.into())])
+    }
+
+    pub fn description(self, description: impl Into<String>) -> Head {
+        self.meta("description", description)
+    }
+
+    // e.g. "width=device-width, initial-scale=1"
+    pub fn viewport(self, viewport: impl Into<String>) -> Head {
+        self.meta("viewport", viewport)
+    }
+
+    // Open Graph tag, e.g. og("title", "Login") adds <meta property="og:title" content="Login">
+    pub fn og(self, property: &'static str, content: impl Into<String>) -> Head {
+        self.tag("meta", vec![("property", format!("og:{}", property)), ("content", content.into())])
+    }
+
+    pub fn favicon(self, href: impl Into<String>) -> Head {
+        self.link("icon", href)
+    }
+
+    pub fn stylesheet(self, href: impl Into<String>) -> Head {
+        self.link("stylesheet", href)
+    }
+
+    // Stylesheet of a web font service, preceded by a preconnect to its origin.
+    // Relative hrefs are on the page's own origin, which needs no preconnect.
+    pub fn font(self, href: impl Into<String>) -> Head {
+        let href = href.into();
+        match origin(&href) {
+            Some(origin) => self.link("preconnect", origin).link("stylesheet", href),
+            None => self.link("stylesheet", href),
+        }
+    }
+
+    // Declares a web font for the page. Its first file is preloaded, so text in it
+    // is less likely to flash in a fallback font.
+    pub fn font_face(self, face: &FontFace) -> Head {
+        let head = match face.sources.first() {
+            Some(url) => {
+                let mut attributes = vec![
+                    ("rel", "preload".to_string()),
+                    ("href", url.to_string()),
+                    ("as", "font".to_string()),
+                    ("crossorigin", String::new()),
+                ];
+                if let Some(format) = font::format(url) {
+                    attributes.push(("type", format!("font/{}", format)));
+                }
+                self.tag("link", attributes)
+            }
+            None => self,
+        };
+        head.text_tag("style", face.css())
+    }
+
+    pub fn link(self, rel: &'static str, href: impl Into<String>) -> Head {
+        self.tag("link", vec![("rel", rel.to_string()), ("href", href.into())])
+    }
+
+    fn tag(mut self, tag: &'static str, attributes: Vec<(&'static str, String)>) -> Head {
+        self.tags.push(Tag { tag, attributes, text: None });
+        self
+    }
+
+    fn text_tag(mut self, tag: &'static str, text: String) -> Head {
+        self.tags.push(Tag { tag, attributes: Vec::new(), text: Some(text) });
+        self
+    }
+
+    // Adds the tags to the backend's head and returns them, so they can be removed later
+    pub fn mount(&self, backend: &dyn Backend) -> Result<Vec<Element>, Error> {
+        if let Some(title) = &self.title {
+            backend.set_title(title)?;
+        }
+
+        let head = backend.head()?;
+        let mut elements = Vec::new();
+        for tag in &self.tags {
+            let element = backend.create_element(tag.tag)?;
+            for (name, value) in &tag.attributes {
+                element.set_attribute(name, value)?;
+            }
+            if let Some(text) = &tag.text {
+                element.set_text(text);
+            }
+            element.set_attribute(MARKER, "")?;
+            head.append_child(&element)?;
+            elements.push(element);
+        }
+        Ok(elements)
+    }
+}
+
+impl Default for Head {
+    fn default() -> Self {
+        Head::new()
+    }
+}
+
+// e.g. "https://fonts.googleapis.com" for "https://fonts.googleapis.com/css2?family=Inter",
+// None for relative URLs
+fn origin(href: &str) -> Option<&str> {
+    let rest = href.strip_prefix("https://").or_else(|| href.strip_prefix("http://"))?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    if host.is_empty() {
+        return None;
+    }
+    Some(&href[..href.len() - rest.len() + host.len()])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::memory::{MemoryBackend, MemoryNode};
+    use crate::window::head::Head;
+
+    fn links(head: Head) -> Vec<(Option<String>, Option<String>)> {
+        let backend = MemoryBackend::new();
+        head.mount(&backend).unwrap().iter()
+            .map(|e| MemoryNode::of(e).unwrap())
+            .map(|node| (node.attribute("rel"), node.attribute("href")))
+            .collect()
+    }
+
+    #[test]
+    fn preconnects_to_font_services() {
+        assert_eq!(links(Head::new().font("https://fonts.googleapis.com/css2?family=Inter")), vec![
+            (Some("preconnect".to_string()), Some("https://fonts.googleapis.com".to_string())),
+            (Some("stylesheet".to_string()), Some("https://fonts.googleapis.com/css2?family=Inter".to_string())),
+        ]);
+        assert_eq!(links(Head::new().font("http://fonts.example.com?family=Inter"))[0].1.as_deref(),
+                   Some("http://fonts.example.com"));
+    }
+
+    #[test]
+    fn relative_fonts_need_no_preconnect() {
+        for href in ["/fonts/inter.css", "fonts/inter.css", "//fonts.example.com/inter.css", "https:///inter.css"] {
+            assert_eq!(links(Head::new().font(href)), vec![(Some("stylesheet".to_string()), Some(href.to_string()))]);
+        }
+    }
+}
+
@@ -1,2 +1,7 @@
 pub mod window;
 pub mod page;
+pub mod ssr;
+pub mod router;
+pub mod head;
+pub mod scheme;
+pub mod fonts;
\ No newline at end of file
@@ -1,7 +1,7 @@
 use crate::view::view;
+use crate::window::head::Head;
 
 pub trait Page {
-    // TODO
-    // fn head(&self) -> ???;
+    fn head(&self) -> Head { Head::new() }
     fn body(&self) -> Box<dyn view::Viewable>;
 }
\ No newline at end of file
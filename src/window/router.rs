@@ -0,0 +1,155 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use crate::window::page::Page;
+use crate::window::window::Window;
+
+pub type Params = HashMap<String, String>;
+
+type PageFn = Box<dyn Fn(&Params) -> Box<dyn Page>>;
+
+thread_local! {
+    // The built window that owns the router, used by Link and navigate
+    static ACTIVE: RefCell<Option<Window>> = const { RefCell::new(None) };
+}
+
+enum Segment {
+    Static(String),
+    Param(String),
+}
+
+struct Route {
+    segments: Vec<Segment>,
+    page: PageFn,
+}
+
+impl Route {
+    fn matches(&self, path: &str) -> Option<Params> {
+        let parts = split(path);
+        if parts.len() != self.segments.len() {
+            return None;
+        }
+
+        let mut params = Params::new();
+        for (segment, part) in self.segments.iter().zip(parts) {
+            match segment {
+                Segment::Static(s) if s == part => {}
+                Segment::Static(_) => return None,
+                Segment::Param(name) => {
+                    params.insert(name.clone(), part.to_string());
+                }
+            }
+        }
+        Some(params)
+    }
+}
+
+// Router maps URL paths to pages. A segment starting with ':' is a parameter,
+// e.g. "/users/:id" matches "/users/42" with id = "42".
+pub struct Router {
+    routes: Vec<Route>,
+    fallback: Option<PageFn>,
+}
+
+impl Router {
+    pub fn new() -> Router {
+        Router { routes: Vec::default(), fallback: None }
+    }
+
+    pub fn route(mut self, path: &str, page: impl Fn(&Params) -> Box<dyn Page> + 'static) -> Router {
+        let segments = split(path).into_iter()
+            .map(|s| match s.strip_prefix(':') {
+                Some(name) => Segment::Param(name.to_string()),
+                None => Segment::Static(s.to_string()),
+            })
+            .collect();
+        self.routes.push(Route { segments, page: Box::new(page) });
+        self
+    }
+
+    // Page shown when no route matches, e.g. a not found page
+    pub fn fallback(mut self, page: impl Fn(&Params) -> Box<dyn Page> + 'static) -> Router {
+        self.fallback = Some(Box::new(page));
+        self
+    }
+
+    // Returns the page of the first route matching the path
+    pub fn resolve(&self, path: &str) -> Option<Box<dyn Page>> {
+        let path = path.split(['?', '#']).next().unwrap_or("");
+        for route in &self.routes {
+            if let Some(params) = route.matches(path) {
+                return Some((route.page)(&params));
+            }
+        }
+        self.fallback.as_ref().map(|page| page(&Params::new()))
+    }
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Router::new()
+    }
+}
+
+fn split(path: &str) -> Vec<&str> {
+    path.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+pub(crate) fn activate(window: Window) {
+    ACTIVE.with(|a| *a.borrow_mut() = Some(window));
+}
+
+// navigate moves the active window to path without reloading the document.
+// Returns false when there is no routed window or no route matches, so the caller
+// can fall back to a regular page load.
+pub fn navigate(path: &str) -> bool {
+    let window = ACTIVE.with(|a| a.borrow().clone());
+    match window {
+        Some(window) => window.navigate(path),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::window::page::Page;
+    use crate::window::router::{Params, Router};
+
+    fn unused(_: &Params) -> Box<dyn Page> {
+        unreachable!()
+    }
+
+    fn params(router: &Router, path: &str) -> Option<Vec<(String, String)>> {
+        let mut params: Vec<(String, String)> = router.routes[0].matches(path)?.into_iter().collect();
+        params.sort();
+        Some(params)
+    }
+
+    #[test]
+    fn matches_static_and_param_segments() {
+        let router = Router::new().route("/users/:id/posts/:post", unused);
+        assert_eq!(params(&router, "/users/42/posts/7"), Some(vec![
+            ("id".to_string(), "42".to_string()),
+            ("post".to_string(), "7".to_string()),
+        ]));
+        assert_eq!(params(&router, "/users/42/comments/7"), None);
+    }
+
+    #[test]
+    fn ignores_trailing_slashes() {
+        let router = Router::new().route("/users/:id", unused);
+        assert_eq!(params(&router, "/users/42/"), Some(vec![("id".to_string(), "42".to_string())]));
+
+        let root = Router::new().route("/", unused);
+        assert_eq!(params(&root, "/"), Some(Vec::new()));
+        assert_eq!(params(&root, ""), Some(Vec::new()));
+    }
+
+    #[test]
+    fn needs_every_segment() {
+        let router = Router::new().route("/users/:id", unused);
+        assert_eq!(params(&router, "/users"), None);
+        assert_eq!(params(&router, "/users/42/posts"), None);
+        assert_eq!(params(&router, "/teams/42"), None);
+        assert!(router.resolve("/teams/42").is_none());
+    }
+}
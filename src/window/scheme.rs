@@ -0,0 +1,127 @@
+use gloo::events::EventListener;
+use gloo::storage::{LocalStorage, Storage};
+use crate::view::theme::Mode;
+
+// Local storage key the user's choice is kept under
+const KEY: &str = "brume-color-scheme";
+
+const DARK_QUERY: &str = "(prefers-color-scheme: dark)";
+
+// ColorScheme is the user's choice between the light and dark mode of the theme
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColorScheme {
+    // Follows prefers-color-scheme of the browser
+    System,
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorScheme::System => "system",
+            ColorScheme::Light => "light",
+            ColorScheme::Dark => "dark",
+        }
+    }
+}
+
+// color_scheme returns the stored choice, System if the user has not made one
+pub fn color_scheme() -> ColorScheme {
+    match LocalStorage::get::<String>(KEY).ok().as_deref() {
+        Some("light") => ColorScheme::Light,
+        Some("dark") => ColorScheme::Dark,
+        _ => ColorScheme::System,
+    }
+}
+
+// set_color_scheme stores the choice and switches the mode to it.
+// System forgets the choice, so the mode follows the browser again.
+pub fn set_color_scheme(scheme: ColorScheme) {
+    match scheme {
+        ColorScheme::System => LocalStorage::delete(KEY),
+        _ => {
+            if let Err(e) = LocalStorage::set(KEY, scheme.name()) {
+                gloo::console::error!(e.to_string());
+            }
+        }
+    }
+    apply();
+}
+
+// follow sets the mode from the stored choice or the browser, and keeps it in line
+// with the browser for as long as the returned listener lives
+pub(crate) fn follow() -> Option<EventListener> {
+    apply();
+    let query = web_sys::window()?.match_media(DARK_QUERY).ok()??;
+    Some(EventListener::new(&query, "change", |_| apply()))
+}
+
+fn apply() {
+    mode(color_scheme(), system_mode()).set();
+}
+
+// The mode to show: the user's choice, or the browser's when there is none
+fn mode(scheme: ColorScheme, system: Mode) -> Mode {
+    match scheme {
+        ColorScheme::System => system,
+        ColorScheme::Light => Mode::Light,
+        ColorScheme::Dark => Mode::Dark,
+    }
+}
+
+fn system_mode() -> Mode {
+    let dark = web_sys::window()
+        .and_then(|w| w.match_media(DARK_QUERY).ok().flatten())
+        .map(|query| query.matches())
+        .unwrap_or(false);
+    if dark { Mode::Dark } else { Mode::Light }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use crate::backend::backend::Backend;
+    use crate::backend::memory::{MemoryBackend, MemoryNode};
+    use crate::view::stylesheet::StyleSheet;
+    use crate::view::theme::Mode;
+    use crate::view::view::Viewable;
+    use crate::view::widget::Button;
+    use crate::window::scheme::{mode, ColorScheme};
+
+    #[test]
+    fn a_chosen_scheme_beats_the_system_one() {
+        assert_eq!(mode(ColorScheme::System, Mode::Dark), Mode::Dark);
+        assert_eq!(mode(ColorScheme::System, Mode::Light), Mode::Light);
+        assert_eq!(mode(ColorScheme::Light, Mode::Dark), Mode::Light);
+        assert_eq!(mode(ColorScheme::Dark, Mode::Light), Mode::Dark);
+    }
+
+    #[test]
+    fn widgets_take_the_palette_of_the_mode() {
+        let build = |button: &mut Button| {
+            let backend = MemoryBackend::new().with_style_sheet(Rc::new(StyleSheet::new()));
+            let element = button.build(&backend).unwrap();
+            let node = MemoryNode::of(&element).unwrap();
+            (node.styles(), backend.style_sheet().css())
+        };
+
+        mode(ColorScheme::System, Mode::Light).set();
+        let (disabled, _) = build(&mut Button::disabled("Off"));
+        assert_eq!(disabled.get("color").map(String::as_str), Some("#D0C9D6"));
+        assert_eq!(disabled.get("background").map(String::as_str), Some("#FBE4E8"));
+        let (primary, css) = build(&mut Button::primary("Save"));
+        assert_eq!(primary.get("background").map(String::as_str), Some("#4256F6"));
+        assert!(css.contains(":hover { background: #2A40F5 !important }"), "{}", css);
+
+        mode(ColorScheme::Dark, Mode::Light).set();
+        let (disabled, _) = build(&mut Button::disabled("Off"));
+        assert_eq!(disabled.get("color").map(String::as_str), Some("#6B5F73"));
+        assert_eq!(disabled.get("background").map(String::as_str), Some("#4A1F29"));
+        // Hovering lightens instead of darkens on a dark background
+        let (_, css) = build(&mut Button::primary("Save"));
+        assert!(css.contains(":hover { background: #5A6CF7 !important }"), "{}", css);
+
+        Mode::Light.set();
+    }
+}
@@ -0,0 +1,108 @@
+use std::rc::Rc;
+use crate::backend::memory::MemoryBackend;
+use crate::view::stylesheet::StyleSheet;
+use crate::view::view;
+use crate::window::page::Page;
+
+// render_to_string builds the page body into an in-memory tree and serializes it as HTML.
+// Use it to pre-render pages on the server or at build time. Styles are inline, so
+// generated rules such as state styles are left out; render_page includes them.
+pub fn render_to_string(page: &dyn Page) -> Result<String, view::Error> {
+    let backend = MemoryBackend::new();
+    page.body().build(&backend)?;
+    Ok(backend.root().to_string())
+}
+
+// render_head_to_string serializes the title and tags of the page's head as HTML
+pub fn render_head_to_string(page: &dyn Page) -> Result<String, view::Error> {
+    let backend = MemoryBackend::new();
+    page.head().mount(&backend)?;
+    Ok(backend.head_root().to_string())
+}
+
+pub struct Rendered {
+    // Title, head tags and the generated style sheet
+    pub head: String,
+    pub body: String,
+}
+
+// render_page renders the page with its styles compiled into a style sheet in the head,
+// matching a window that uses with_style_sheet
+pub fn render_page(page: &dyn Page) -> Result<Rendered, view::Error> {
+    let sheet = Rc::new(StyleSheet::compiled());
+    let backend = MemoryBackend::new().with_style_sheet(Rc::clone(&sheet));
+    page.head().mount(&backend)?;
+    page.body().build(&backend)?;
+    sheet.flush(&backend)?;
+    Ok(Rendered { head: backend.head_root().to_string(), body: backend.root().to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::index::Login;
+    use crate::view::view::Viewable;
+    use crate::view::widget::{Body, Text};
+    use crate::window::head::Head;
+    use crate::window::page::Page;
+    use crate::window::ssr::{render_head_to_string, render_page, render_to_string};
+
+    struct Notes {}
+
+    impl Page for Notes {
+        fn head(&self) -> Head {
+            Head::new().title("Tom & Jerry").description("Notes <draft>")
+        }
+
+        fn body(&self) -> Box<dyn Viewable> {
+            Body::new(Text::new("1 < 2 & \"so on\"")).make()
+        }
+    }
+
+    #[test]
+    fn renders_the_body_with_inline_styles() {
+        let html = render_to_string(&Notes {}).unwrap();
+        assert!(html.starts_with("<body style=\""), "{}", html);
+        assert!(html.contains(">1 &lt; 2 &amp; \"so on\"</"), "{}", html);
+        assert!(!html.contains("class="), "{}", html);
+
+        let login = render_to_string(Login::new().as_ref()).unwrap();
+        assert!(login.contains("<h2 style=\"color: #000000; font-family: sans-serif; font-size: 22px\">Login</h2>"));
+        assert!(login.contains("<input class=\""));
+        assert!(login.contains(" placeholder=\"Username\" style=\""));
+        assert!(login.contains("padding-top: 16px"));
+        assert!(login.contains("cursor: pointer"));
+        assert!(login.contains(">Submit</button></div></div></body>"));
+    }
+
+    #[test]
+    fn renders_the_head() {
+        assert_eq!(render_head_to_string(&Notes {}).unwrap(),
+                   "<head><title>Tom &amp; Jerry</title>\
+                   <meta content=\"Notes &lt;draft&gt;\" data-head=\"\" name=\"description\"></head>");
+        assert_eq!(render_head_to_string(Login::new().as_ref()).unwrap(),
+                   "<head><title>Login</title>\
+                   <meta content=\"width=device-width, initial-scale=1\" data-head=\"\" name=\"viewport\"></head>");
+    }
+
+    #[test]
+    fn renders_a_page_with_compiled_styles() {
+        let rendered = render_page(Login::new().as_ref()).unwrap();
+        assert!(rendered.head.starts_with("<head><title>Login</title><meta "));
+        assert!(rendered.head.contains("<style data-style-sheet=\"\">"));
+        assert!(rendered.head.contains(":hover { background: #2A40F5 }"));
+        assert!(rendered.head.contains("@media (min-width: 768px) { .b-"));
+        assert!(rendered.head.ends_with("</style></head>"));
+
+        // Every style is a class now, each backed by a rule in the head
+        assert!(!rendered.body.contains("style="), "{}", rendered.body);
+        let classes: Vec<&str> = rendered.body.split("class=\"").skip(1)
+            .flat_map(|rest| rest.split('"').next().unwrap().split(' '))
+            .collect();
+        assert!(!classes.is_empty());
+        for class in classes {
+            assert!(rendered.head.contains(&format!(".{}", class)), "{}", class);
+        }
+        assert!(rendered.body.contains("placeholder=\"Password\""));
+        assert!(rendered.body.contains(">Submit</button>"));
+    }
+}
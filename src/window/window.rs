@@ -1,24 +1,259 @@
-use std::fmt::Error;
-use web_sys::HtmlElement;
-use crate::view::size::Size;
-use crate::view::{view, widget};
+use std::cell::RefCell;
+use std::rc::Rc;
+use gloo::events::EventListener;
+use gloo::timers::callback::Timeout;
+use wasm_bindgen::JsValue;
+use crate::backend::backend::{Backend, Element};
+use crate::backend::web::WebBackend;
+use crate::view::{diff, stylesheet, view};
+use crate::view::breakpoint::Breakpoints;
+use crate::view::reactive::Effect;
+use crate::view::theme::{Mode, Theme};
 use crate::window::page;
+use crate::window::router::{self, Router};
+use crate::window::scheme;
 
-pub struct Window {
+struct State {
     page: Box<dyn page::Page>,
-    document: web_sys::Document,
+    // The mounted widget tree. It owns the event listeners, so it lives as long as the window.
+    body: Option<Box<dyn view::Viewable>>,
+    // What body rendered, in memory, for the next rerender to compare against
+    snapshot: Option<Element>,
+    router: Option<Router>,
+    // Head tags added by the current page
+    head: Vec<Element>,
+    // Title of the document before any page set one, shown by pages without a title
+    title: String,
+    // Shows the matching page on back and forward navigation
+    popstate: Option<EventListener>,
+    // Follows the browser's color scheme, and re-renders the page when the mode changes
+    color_scheme: Option<EventListener>,
+    mode: Option<Effect>,
+}
+
+// Window is a handle to the document the page is shown in. Clones share the same state.
+#[derive(Clone)]
+pub struct Window {
+    state: Rc<RefCell<State>>,
+    backend: Rc<WebBackend>,
 }
 
 impl Window {
     pub fn new(page: Box<dyn page::Page>) -> Option<Window> {
-        let window = web_sys::window()?;
-        let document = window.document()?;
-        Some(Window { page, document })
+        Window::create(page, None)
+    }
+
+    // with_router starts on the page the router maps the current location to.
+    // Links to paths of the same site are then followed without reloading the document.
+    pub fn with_router(router: Router) -> Option<Window> {
+        let page = router.resolve(&location()?)?;
+        Window::create(page, Some(router))
+    }
+
+    fn create(page: Box<dyn page::Page>, router: Option<Router>) -> Option<Window> {
+        let backend = WebBackend::new()?;
+        let state = State {
+            page,
+            body: None,
+            snapshot: None,
+            router,
+            head: Vec::new(),
+            title: backend.document().title(),
+            popstate: None,
+            color_scheme: None,
+            mode: None,
+        };
+        Some(Window { state: Rc::new(RefCell::new(state)), backend: Rc::new(backend) })
+    }
+
+    // with_style_sheet makes widgets share generated CSS classes instead of inline styles
+    pub fn with_style_sheet(self) -> Window {
+        self.backend.enable_style_sheet();
+        self
+    }
+
+    // with_theme styles the built-in widgets of every page with theme
+    pub fn with_theme(self, theme: Theme) -> Window {
+        theme.install();
+        self
+    }
+
+    // with_breakpoints sets the viewport widths breakpoint styles apply from
+    pub fn with_breakpoints(self, breakpoints: Breakpoints) -> Window {
+        breakpoints.install();
+        self
     }
 
     pub fn build(self) -> Result<Window, view::Error> {
-        // TODO: Add header initializer here
-        self.page.body().build(&self.document)?;
+        self.follow_color_scheme();
+        self.mount_head()?;
+        let mut body = self.state.borrow().page.body();
+        let snapshot = diff::snapshot(self.backend.as_ref(), body.as_mut())?;
+        body.build(self.backend.as_ref())?;
+        stylesheet::flush(self.backend.as_ref())?;
+        let mut state = self.state.borrow_mut();
+        state.body = Some(body);
+        state.snapshot = Some(snapshot);
+        drop(state);
+        self.activate_router();
         Ok(self)
     }
-}
\ No newline at end of file
+
+    // rerender builds the page again and patches the document with what changed,
+    // reusing the elements of the current tree
+    pub fn rerender(&self) -> Result<(), view::Error> {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+        let mut body = state.page.body();
+        let snapshot = match (state.body.as_mut(), state.snapshot.as_ref()) {
+            (Some(previous), Some(snapshot)) =>
+                diff::rerender(self.backend.as_ref(), previous.as_mut(), snapshot, body.as_mut())?,
+            _ => {
+                let snapshot = diff::snapshot(self.backend.as_ref(), body.as_mut())?;
+                body.build(self.backend.as_ref())?;
+                stylesheet::flush(self.backend.as_ref())?;
+                snapshot
+            }
+        };
+        state.body = Some(body);
+        state.snapshot = Some(snapshot);
+        Ok(())
+    }
+
+    // hydrate attaches the page to markup that is already in the document, e.g. from
+    // ssr::render_to_string, reusing its elements and wiring up event listeners.
+    // If the markup does not match the page it is replaced by a fresh build.
+    pub fn hydrate(self) -> Result<Window, view::Error> {
+        self.follow_color_scheme();
+        let mut body = self.state.borrow().page.body();
+        let snapshot = diff::snapshot(self.backend.as_ref(), body.as_mut())?;
+        self.backend.start_hydration()?;
+        let hydrated = body.build(self.backend.as_ref()).and_then(|_| self.backend.finish_hydration());
+
+        match hydrated {
+            Ok(()) => {
+                let mut state = self.state.borrow_mut();
+                state.body = Some(body);
+                state.snapshot = Some(snapshot);
+                drop(state);
+                stylesheet::flush(self.backend.as_ref())?;
+                self.backend.remove_head_tags()?;
+                self.mount_head()?;
+                self.activate_router();
+                Ok(self)
+            }
+            Err(view::Error::HydrationMismatch(_)) => {
+                drop(body);
+                self.backend.abort_hydration()?;
+                self.build()
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // navigate pushes a history entry for path and shows the page the router maps it to.
+    // Returns false if the window has no router or no route matches.
+    pub fn navigate(&self, path: &str) -> bool {
+        // Only paths of this site are routed, everything else is a regular page load
+        if !path.starts_with('/') || path.starts_with("//") {
+            return false;
+        }
+
+        let page = match self.state.borrow().router.as_ref().and_then(|r| r.resolve(path)) {
+            Some(page) => page,
+            None => return false,
+        };
+
+        let history = web_sys::window().and_then(|w| w.history().ok());
+        match history {
+            Some(history) if history.push_state_with_url(&JsValue::NULL, "", Some(path)).is_ok() => {}
+            _ => return false,
+        }
+
+        // Navigation usually starts in a listener of the current body, which must not be
+        // dropped while it runs, so the new page is shown once the event is handled
+        let window = self.clone();
+        Timeout::new(0, move || window.show(page)).forget();
+        true
+    }
+
+    fn show(&self, page: Box<dyn page::Page>) {
+        self.state.borrow_mut().page = page;
+        if let Err(e) = self.mount_head().and_then(|_| self.rerender()) {
+            gloo::console::error!(e.to_string());
+        }
+    }
+
+    // Sets the mode from the user's or the browser's color scheme, and shows the page
+    // in the other mode whenever it is switched
+    fn follow_color_scheme(&self) {
+        if self.state.borrow().mode.is_some() {
+            return;
+        }
+
+        let color_scheme = scheme::follow();
+        let window = self.clone();
+        let mut first = true;
+        let mode = Effect::new(move || {
+            Mode::current();
+            if first {
+                first = false;
+                return;
+            }
+
+            // Like navigation, switching usually starts in a listener of the current body
+            let window = window.clone();
+            Timeout::new(0, move || {
+                if let Err(e) = window.rerender() {
+                    gloo::console::error!(e.to_string());
+                }
+            }).forget();
+        });
+
+        let mut state = self.state.borrow_mut();
+        state.color_scheme = color_scheme;
+        state.mode = Some(mode);
+    }
+
+    // Replaces the head tags of the previous page with those of the current one
+    fn mount_head(&self) -> Result<(), view::Error> {
+        let mut state = self.state.borrow_mut();
+        let head = self.backend.head()?;
+        for element in state.head.drain(..) {
+            head.remove_child(&element)?;
+        }
+        let page_head = state.page.head();
+        state.head = page_head.mount(self.backend.as_ref())?;
+        if page_head.get_title().is_none() {
+            self.backend.set_title(&state.title)?;
+        }
+        Ok(())
+    }
+
+    fn activate_router(&self) {
+        if self.state.borrow().router.is_none() {
+            return;
+        }
+
+        let window = match web_sys::window() {
+            Some(w) => w,
+            None => return,
+        };
+
+        let handle = self.clone();
+        let popstate = EventListener::new(&window, "popstate", move |_| {
+            let page = location().and_then(|path| {
+                handle.state.borrow().router.as_ref().and_then(|r| r.resolve(&path))
+            });
+            if let Some(page) = page {
+                handle.show(page);
+            }
+        });
+        self.state.borrow_mut().popstate = Some(popstate);
+        router::activate(self.clone());
+    }
+}
+
+fn location() -> Option<String> {
+    web_sys::window()?.location().pathname().ok()
+}